@@ -0,0 +1,299 @@
+use crate::geometry::grid::Grid;
+use crate::geometry::point::Point2D;
+use crate::geometry::region::Connectivity;
+
+//what the cells just beyond the edge of an automaton look like
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub(crate) enum Boundary {
+    #[default]
+    Dead,
+    Live,
+    //the opposite edge
+    Wrap
+}
+
+//how the next state of a cell follows from its neighbourhood
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Rule {
+    //one-dimensional, bit 4 * left + 2 * cell + right of the Wolfram code is the next state;
+    //every row evolves on its own
+    Elementary(u8),
+    //bit n of birth tells whether a dead cell with n live neighbours comes alive,
+    //bit n of survive whether a live one stays alive
+    Totalistic{birth: u16, survive: u16, neighborhood: Connectivity}
+}
+
+#[allow(dead_code)]
+impl Rule {
+    pub(crate) fn elementary(next: impl Fn(bool, bool, bool) -> bool) -> Self{
+        let code = (0..8u8)
+            .filter(|pattern| next(pattern & 4 != 0, pattern & 2 != 0, pattern & 1 != 0))
+            .fold(0, |code, pattern| code | 1 << pattern);
+        Rule::Elementary(code)
+    }
+
+    pub(crate) fn totalistic(birth: &[usize], survive: &[usize], neighborhood: Connectivity) -> Self{
+        assert!(birth.iter().chain(survive).all(|&n| n <= 8), "Neighbour counts cannot exceed 8");
+        let mask = |counts: &[usize]| counts.iter().fold(0u16, |mask, n| mask | 1 << n);
+        Rule::Totalistic{birth: mask(birth), survive: mask(survive), neighborhood}
+    }
+
+    //next state from the current one and the number of live neighbours
+    pub(crate) fn from_fn(next: impl Fn(bool, usize) -> bool, neighborhood: Connectivity) -> Self{
+        let n_max = neighborhood.directions().len();
+        let birth: Vec<usize> = (0..=n_max).filter(|&n| next(false, n)).collect();
+        let survive: Vec<usize> = (0..=n_max).filter(|&n| next(true, n)).collect();
+        Self::totalistic(&birth, &survive, neighborhood)
+    }
+
+    //Conway's Game of Life, B3/S23
+    pub(crate) fn life() -> Self{
+        Self::totalistic(&[3], &[2, 3], Connectivity::Eight)
+    }
+}
+
+//cellular automaton on a width x height rectangle of cells, each row packed 64 cells to a word
+//so that one step works on whole words; bits beyond the width are always 0
+#[derive(Debug, Clone)]
+pub(crate) struct Automaton {
+    width: usize,
+    height: usize,
+    rows: Vec<Vec<u64>>,
+    rule: Rule,
+    boundary: Boundary,
+    //cells held at a fixed state, with the state they are held at
+    pinned: Vec<Vec<u64>>,
+    pinned_state: Vec<Vec<u64>>,
+    generation: usize
+}
+
+#[allow(dead_code)]
+impl Automaton {
+    pub(crate) fn new(width: usize, height: usize, rule: Rule) -> Self{
+        assert!(width > 0, "Automaton needs at least one column");
+        let empty = vec![vec![0; width.div_ceil(64)]; height];
+        Self{
+            width,
+            height,
+            rows: empty.clone(),
+            rule,
+            boundary: Boundary::Dead,
+            pinned: empty.clone(),
+            pinned_state: empty,
+            generation: 0
+        }
+    }
+
+    pub(crate) fn from_row(cells: &[bool], rule: Rule) -> Self{
+        let mut automaton = Self::new(cells.len(), 1, rule);
+        for (x, &alive) in cells.iter().enumerate() {
+            automaton.set(&Point2D::new(x, 0), alive);
+        }
+        automaton
+    }
+
+    pub(crate) fn from_grid<T>(grid: &Grid<T>, alive: impl Fn(&T) -> bool, rule: Rule) -> Self{
+        let mut automaton = Self::new(grid.width(), grid.height(), rule);
+        for (p, value) in grid.iter() {
+            automaton.set(&p, alive(value));
+        }
+        automaton
+    }
+
+    pub(crate) fn boundary(mut self, boundary: Boundary) -> Self{
+        self.boundary = boundary;
+        self
+    }
+
+    //holds the cell at the given state from now on
+    pub(crate) fn pin(mut self, point: &Point2D, alive: bool) -> Self{
+        let (y, word, bit) = self.locate(point);
+        self.pinned[y][word] |= bit;
+        if alive { self.pinned_state[y][word] |= bit } else { self.pinned_state[y][word] &= !bit }
+        self.set(point, alive);
+        self
+    }
+
+    pub(crate) fn width(&self) -> usize{
+        self.width
+    }
+
+    pub(crate) fn height(&self) -> usize{
+        self.height
+    }
+
+    //number of steps taken
+    pub(crate) fn generation(&self) -> usize{
+        self.generation
+    }
+
+    fn locate(&self, point: &Point2D) -> (usize, usize, u64){
+        assert!(!point.is_out_of_bounds(self.width, self.height), "{} is outside of the automaton", point);
+        let (x, y) = (*point.x() as usize, *point.y() as usize);
+        (y, x / 64, 1 << (x % 64))
+    }
+
+    pub(crate) fn get(&self, point: &Point2D) -> bool{
+        let (y, word, bit) = self.locate(point);
+        self.rows[y][word] & bit != 0
+    }
+
+    pub(crate) fn set(&mut self, point: &Point2D, alive: bool){
+        let (y, word, bit) = self.locate(point);
+        if alive { self.rows[y][word] |= bit } else { self.rows[y][word] &= !bit }
+    }
+
+    pub(crate) fn population(&self) -> usize{
+        (0..self.height).map(|y| self.row_population(y)).sum()
+    }
+
+    pub(crate) fn row_population(&self, y: usize) -> usize{
+        self.rows[y].iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub(crate) fn live_cells(&self) -> impl Iterator<Item = Point2D> + '_{
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| Point2D::new(x, y)))
+            .filter(|p| self.get(p))
+    }
+
+    pub(crate) fn to_grid(&self) -> Grid<bool>{
+        Grid::from_fn(self.width, self.height, |x, y| self.get(&Point2D::new(x, y)))
+    }
+
+    //all cells of a row alive, bits beyond the width left at 0
+    fn full_row(&self) -> Vec<u64>{
+        let mut row = vec![!0; self.width.div_ceil(64)];
+        self.clear_padding(&mut row);
+        row
+    }
+
+    fn clear_padding(&self, row: &mut [u64]){
+        let used = self.width % 64;
+        if let Some(last) = row.last_mut().filter(|_| used > 0) {
+            *last &= (1 << used) - 1;
+        }
+    }
+
+    //cell x of the result is cell x - 1 of row, the left neighbour of every cell
+    fn left_neighbors(&self, row: &[u64]) -> Vec<u64>{
+        let fill = match self.boundary {
+            Boundary::Dead => 0,
+            Boundary::Live => 1,
+            Boundary::Wrap => (row[(self.width - 1) / 64] >> ((self.width - 1) % 64)) & 1
+        };
+        let mut shifted: Vec<u64> = (0..row.len())
+            .map(|i| row[i] << 1 | if i == 0 { fill } else { row[i - 1] >> 63 })
+            .collect();
+        self.clear_padding(&mut shifted);
+        shifted
+    }
+
+    //cell x of the result is cell x + 1 of row
+    fn right_neighbors(&self, row: &[u64]) -> Vec<u64>{
+        let fill = match self.boundary {
+            Boundary::Dead => 0,
+            Boundary::Live => 1,
+            Boundary::Wrap => row[0] & 1
+        };
+        let last = row.len() - 1;
+        let mut shifted: Vec<u64> = (0..row.len())
+            .map(|i| row[i] >> 1 | if i == last { 0 } else { row[i + 1] << 63 })
+            .collect();
+        shifted[(self.width - 1) / 64] |= fill << ((self.width - 1) % 64);
+        shifted
+    }
+
+    //row y + dy, or what the boundary puts there
+    fn neighbor_row(&self, y: usize, dy: isize) -> Vec<u64>{
+        let ny = y as isize + dy;
+        if (0..self.height as isize).contains(&ny) {
+            return self.rows[ny as usize].clone();
+        }
+        match self.boundary {
+            Boundary::Dead => vec![0; self.width.div_ceil(64)],
+            Boundary::Live => self.full_row(),
+            Boundary::Wrap => self.rows[ny.rem_euclid(self.height as isize) as usize].clone()
+        }
+    }
+
+    fn next_row(&self, y: usize) -> Vec<u64>{
+        let row = &self.rows[y];
+        let mut next: Vec<u64> = match self.rule {
+            Rule::Elementary(code) => {
+                let (left, right) = (self.left_neighbors(row), self.right_neighbors(row));
+                (0..row.len())
+                    .map(|i| {
+                        (0..8).filter(|pattern| code >> pattern & 1 == 1).fold(0, |next, pattern| {
+                            let pick = |word: u64, bit: u8| if pattern & bit != 0 { word } else { !word };
+                            next | (pick(left[i], 4) & pick(row[i], 2) & pick(right[i], 1))
+                        })
+                    })
+                    .collect()
+            }
+            Rule::Totalistic{birth, survive, neighborhood} => {
+                let (above, below) = (self.neighbor_row(y, -1), self.neighbor_row(y, 1));
+                let mut planes = vec![above.clone(), self.left_neighbors(row), self.right_neighbors(row), below.clone()];
+                if neighborhood == Connectivity::Eight {
+                    planes.extend([self.left_neighbors(&above), self.right_neighbors(&above),
+                                   self.left_neighbors(&below), self.right_neighbors(&below)]);
+                }
+                (0..row.len())
+                    .map(|i| {
+                        //live neighbours of the 64 cells counted in parallel, bit b of each count in counts[b]
+                        let mut counts = [0u64; 4];
+                        for plane in &planes {
+                            let mut carry = plane[i];
+                            for count in counts.iter_mut() {
+                                (*count, carry) = (*count ^ carry, *count & carry);
+                            }
+                        }
+                        (0..=planes.len()).fold(0, |next, n| {
+                            let has_n = (0..4).fold(!0, |mask, b| mask & if n >> b & 1 == 1 { counts[b] } else { !counts[b] });
+                            let born = if birth >> n & 1 == 1 { !row[i] & has_n } else { 0 };
+                            let stays = if survive >> n & 1 == 1 { row[i] & has_n } else { 0 };
+                            next | born | stays
+                        })
+                    })
+                    .collect()
+            }
+        };
+        for (i, word) in next.iter_mut().enumerate() {
+            *word = (*word & !self.pinned[y][i]) | self.pinned_state[y][i];
+        }
+        self.clear_padding(&mut next);
+        next
+    }
+
+    pub(crate) fn step(&mut self){
+        self.rows = (0..self.height).map(|y| self.next_row(y)).collect();
+        self.generation += 1;
+    }
+
+    //takes n steps, but once the states repeat the remaining full cycles are skipped;
+    //returns the period if one was found. Brent's method keeps only one earlier state around
+    pub(crate) fn run(&mut self, n: usize) -> Option<usize>{
+        let target = self.generation + n;
+        let mut saved = self.rows.clone();
+        let (mut power, mut length) = (1, 0);
+        while self.generation < target {
+            self.step();
+            length += 1;
+            if self.rows == saved {
+                self.generation = target - (target - self.generation) % length;
+                while self.generation < target {
+                    self.step();
+                }
+                return Some(length);
+            }
+            if length == power {
+                saved = self.rows.clone();
+                power *= 2;
+                length = 0;
+            }
+        }
+        None
+    }
+}
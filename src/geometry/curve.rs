@@ -0,0 +1,184 @@
+use crate::geometry::point::Point2D;
+
+//bijection between 0, 1, 2, ... and the cells visited by a curve, in screen coordinates
+pub(crate) trait Curve {
+    fn point(&self, index: usize) -> Point2D;
+
+    //None for cells the curve never visits
+    fn index(&self, p: &Point2D) -> Option<usize>;
+
+    //number of cells, None for curves covering an unbounded area
+    fn len(&self) -> Option<usize>{
+        None
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Point2D> + '_
+    where
+        Self: Sized,
+    {
+        (0..self.len().unwrap_or(usize::MAX)).map(move |index| self.point(index))
+    }
+}
+
+//Ulam spiral: index 0 at the origin, then rings of growing radius walked counterclockwise on screen,
+//each ring starting right above its lower right corner and ending in it
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SquareSpiral;
+
+impl Curve for SquareSpiral {
+    fn point(&self, index: usize) -> Point2D{
+        if index == 0 {
+            return Point2D::origin();
+        }
+        //ring k holds the indices from (2k - 1)^2 to (2k + 1)^2 - 1
+        let ring = index.isqrt().div_ceil(2);
+        let k = ring as isize;
+        let position = (index - (2 * ring - 1).pow(2)) as isize;
+        let (side, rem) = (position / (2 * k), position % (2 * k) + 1);
+        let (x, y) = match side {
+            0 => (k, k - rem),
+            1 => (k - rem, -k),
+            2 => (-k, rem - k),
+            3 => (rem - k, k),
+            _ => unreachable!()
+        };
+        Point2D::new(x, y)
+    }
+
+    fn index(&self, p: &Point2D) -> Option<usize>{
+        let (x, y) = (*p.x(), *p.y());
+        let k = p.chebyshev() as isize;
+        if k == 0 {
+            return Some(0);
+        }
+        let position = if x == k && y < k {
+            k - y
+        } else if y == -k {
+            3 * k - x
+        } else if x == -k {
+            5 * k + y
+        } else {
+            7 * k + x
+        };
+        Some(((2 * k - 1).pow(2) + position - 1) as usize)
+    }
+}
+
+//Cantor enumeration of the quadrant x, y >= 0: anti-diagonals of growing length,
+//each one walked from its lowest cell on screen (x = 0) up and to the right
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct CantorDiagonal;
+
+impl Curve for CantorDiagonal {
+    fn point(&self, index: usize) -> Point2D{
+        //largest diagonal d with d(d + 1)/2 <= index
+        let mut d = (((8 * index + 1).isqrt()) - 1) / 2;
+        while d * (d + 1) / 2 > index {
+            d -= 1;
+        }
+        let x = index - d * (d + 1) / 2;
+        Point2D::new(x, d - x)
+    }
+
+    fn index(&self, p: &Point2D) -> Option<usize>{
+        let (x, y) = (usize::try_from(*p.x()).ok()?, usize::try_from(*p.y()).ok()?);
+        let d = x + y;
+        Some(d * (d + 1) / 2 + x)
+    }
+}
+
+//Hilbert curve filling the 2^order x 2^order square from (0, 0) to (2^order - 1, 0)
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HilbertCurve {
+    order: u32
+}
+
+#[allow(dead_code)]
+impl HilbertCurve {
+    pub(crate) fn new(order: u32) -> Self{
+        Self{order}
+    }
+
+    pub(crate) fn side(&self) -> usize{
+        1 << self.order
+    }
+
+    //reflects a sub-square so that every quadrant is entered the same way
+    fn rotate(side: usize, x: &mut usize, y: &mut usize, rx: usize, ry: usize){
+        if ry == 0 {
+            if rx == 1 {
+                *x = side - 1 - *x;
+                *y = side - 1 - *y;
+            }
+            std::mem::swap(x, y);
+        }
+    }
+}
+
+impl Curve for HilbertCurve {
+    fn point(&self, index: usize) -> Point2D{
+        let (mut x, mut y, mut t) = (0, 0, index);
+        let mut s = 1;
+        while s < self.side() {
+            let rx = 1 & (t / 2);
+            let ry = 1 & (t ^ rx);
+            Self::rotate(s, &mut x, &mut y, rx, ry);
+            x += s * rx;
+            y += s * ry;
+            t /= 4;
+            s *= 2;
+        }
+        Point2D::new(x, y)
+    }
+
+    fn index(&self, p: &Point2D) -> Option<usize>{
+        let (mut x, mut y) = (usize::try_from(*p.x()).ok()?, usize::try_from(*p.y()).ok()?);
+        if x >= self.side() || y >= self.side() {
+            return None;
+        }
+        let mut index = 0;
+        let mut s = self.side() / 2;
+        while s > 0 {
+            let rx = usize::from(x & s > 0);
+            let ry = usize::from(y & s > 0);
+            index += s * s * ((3 * rx) ^ ry);
+            Self::rotate(self.side(), &mut x, &mut y, rx, ry);
+            s /= 2;
+        }
+        Some(index)
+    }
+
+    fn len(&self) -> Option<usize>{
+        Some(self.side() * self.side())
+    }
+}
+
+//Morton order of the quadrant x, y >= 0: the bits of x and y interleaved, x taking the lower bit
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ZOrderCurve;
+
+#[allow(dead_code)]
+impl ZOrderCurve {
+    fn spread(value: usize) -> usize{
+        (0..usize::BITS as usize / 2).map(|bit| ((value >> bit) & 1) << (2 * bit)).sum()
+    }
+
+    fn compact(value: usize) -> usize{
+        (0..usize::BITS as usize / 2).map(|bit| ((value >> (2 * bit)) & 1) << bit).sum()
+    }
+}
+
+impl Curve for ZOrderCurve {
+    fn point(&self, index: usize) -> Point2D{
+        Point2D::new(Self::compact(index), Self::compact(index >> 1))
+    }
+
+    fn index(&self, p: &Point2D) -> Option<usize>{
+        let (x, y) = (usize::try_from(*p.x()).ok()?, usize::try_from(*p.y()).ok()?);
+        if x.max(y) >> (usize::BITS / 2) > 0 {
+            return None;
+        }
+        Some(Self::spread(x) | (Self::spread(y) << 1))
+    }
+}
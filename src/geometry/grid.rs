@@ -0,0 +1,403 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Index, IndexMut};
+use std::sync::OnceLock;
+use crate::geometry::point::Point2D;
+use crate::geometry::rect::Rect;
+use crate::geometry::symmetry::Symmetry;
+use crate::geometry::topology::Topology;
+use crate::geometry::{Direction, Orientation};
+
+//rectangular grid of cells stored row by row in one vector; the positions of every value
+//are only collected when first asked for, so grids of plain numbers never pay for them
+#[derive(Debug, Clone)]
+pub(crate) struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+    topology: Topology,
+    index: OnceLock<BTreeMap<T, BTreeSet<Point2D>>>
+}
+
+//saved cells of a grid, together with the index if it was built
+#[derive(Debug, Clone)]
+pub(crate) struct GridSnapshot<T> {
+    cells: Vec<T>,
+    index: Option<BTreeMap<T, BTreeSet<Point2D>>>
+}
+
+//text puzzles are grids of characters
+pub(crate) type Canvas = Grid<char>;
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self{
+            cells: Vec::new(),
+            width: 0,
+            height: 0,
+            orientation: Orientation::Screen,
+            topology: Topology::Bounded,
+            index: OnceLock::new()
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Grid<T> {
+    pub(crate) fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self{
+            cells: vec![fill; width * height],
+            width,
+            height,
+            ..Self::default()
+        }
+    }
+
+    //cell (x, y) set to value(x, y)
+    pub(crate) fn from_fn(width: usize, height: usize, value: impl Fn(usize, usize) -> T) -> Self{
+        Self{
+            cells: (0..width * height).map(|offset| value(offset % width, offset / width)).collect(),
+            width,
+            height,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn from_rows(rows: Vec<Vec<T>>) -> Self{
+        let mut grid = Self::default();
+        for row in rows {
+            grid.add_row(row);
+        }
+        grid
+    }
+
+    pub(crate) fn shape(&self) -> (&usize, &usize){
+        (&self.width, &self.height)
+    }
+
+    pub(crate) fn width(&self) -> usize{
+        self.width
+    }
+
+    pub(crate) fn height(&self) -> usize{
+        self.height
+    }
+
+    pub(crate) fn len(&self) -> usize{
+        self.cells.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool{
+        self.cells.is_empty()
+    }
+
+    pub(crate) fn orientation(&self) -> Orientation{
+        self.orientation
+    }
+
+    //rows are always added top to bottom, in Math orientation the last row ends up at y = 0
+    pub(crate) fn to_orientation(&self, orientation: Orientation) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = if orientation == self.orientation { self.clone() } else { self.flip() };
+        grid.orientation = orientation;
+        grid
+    }
+
+    pub(crate) fn topology(&self) -> Topology{
+        self.topology
+    }
+
+    pub(crate) fn with_topology(mut self, topology: Topology) -> Self{
+        self.topology = topology;
+        self
+    }
+
+    //neighbouring point in the given direction, following the orientation and the topology of the grid;
+    //None when stepping off a bounded grid, and always on an empty grid
+    pub(crate) fn step(&self, point: &Point2D, direction: &Direction) -> Option<Point2D>{
+        self.topology.resolve(&(*point + direction.to_point_in(self.orientation)), &self.bounds()?)
+    }
+
+    pub(crate) fn add_row(&mut self, row: Vec<T>){
+        assert_eq!(self.orientation, Orientation::Screen, "Rows can only be added to a grid in screen orientation");
+        if self.height > 0 {assert_eq!(row.len(), self.width)}
+        else {self.width = row.len()}
+        self.height += 1;
+        self.cells.extend(row);
+        self.index.take();
+    }
+
+    //None for an empty grid, a rectangle always holds at least one cell
+    pub(crate) fn bounds(&self) -> Option<Rect>{
+        (!self.is_empty()).then(|| Rect::from_size(self.width, self.height))
+    }
+
+    pub(crate) fn contains(&self, point: &Point2D) -> bool{
+        !point.is_out_of_bounds(self.width, self.height)
+    }
+
+    //position of a point in the flat storage
+    fn offset(&self, point: &Point2D) -> Option<usize>{
+        if self.contains(point) {
+            Some(*point.y() as usize * self.width + *point.x() as usize)
+        } else {
+            None
+        }
+    }
+
+    fn offset_or_panic(&self, point: &Point2D) -> usize{
+        self.offset(point).unwrap_or_else(|| panic!("{} is outside of the grid", point))
+    }
+
+    fn point_at(&self, offset: usize) -> Point2D{
+        Point2D::new(offset % self.width, offset / self.width)
+    }
+
+    pub(crate) fn get_element(&self, point: &Point2D) -> Option<&T>{
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub(crate) fn row(&self, y: usize) -> &[T]{
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub(crate) fn rows(&self) -> impl Iterator<Item = &[T]>{
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    //every point row by row
+    pub(crate) fn points(&self) -> impl Iterator<Item = Point2D> + '_{
+        (0..self.cells.len()).map(move |offset| self.point_at(offset))
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Point2D, &T)>{
+        self.cells.iter().enumerate().map(move |(offset, value)| (self.point_at(offset), value))
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &T>{
+        self.cells.iter()
+    }
+
+    pub(crate) fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U>{
+        Grid{
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+            orientation: self.orientation,
+            topology: self.topology,
+            index: OnceLock::new()
+        }
+    }
+
+    //grid of the given shape whose cell (x, y) is taken from source(x, y) of this one
+    pub(super) fn remap(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> (usize, usize)) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..width * height)
+            .map(|offset| {
+                let (x, y) = source(offset % width, offset / width);
+                self.cells[y * self.width + x].clone()
+            })
+            .collect();
+        Self{cells, width, height, orientation: self.orientation, topology: self.topology, index: OnceLock::new()}
+    }
+
+    pub(crate) fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transformed(Symmetry::Transpose)
+    }
+
+    pub(crate) fn flip(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transformed(Symmetry::FlipVertical)
+    }
+
+    pub(crate) fn transpose_flip(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transformed(Symmetry::RotateLeft)
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Ord + Clone> Grid<T> {
+    pub(crate) fn elements(&self) -> &BTreeMap<T, BTreeSet<Point2D>>{
+        self.index.get_or_init(|| {
+            let mut elements: BTreeMap<T, BTreeSet<Point2D>> = BTreeMap::new();
+            for (offset, value) in self.cells.iter().enumerate() {
+                elements.entry(value.clone()).or_default().insert(self.point_at(offset));
+            }
+            elements
+        })
+    }
+
+    pub(crate) fn get_element_set(&self) -> BTreeSet<T>{
+        self.elements().keys().cloned().collect()
+    }
+
+    pub(crate) fn try_locate_element(&self, el: &T) -> Result<&BTreeSet<Point2D>, String>
+    where
+        T: fmt::Display,
+    {
+        match self.elements().get(el){
+            None => Err(format!("Cannot locate {}", el)),
+            Some(locations) => Ok(locations)
+        }
+    }
+
+    //moves the point from the set of old to the set of new, if the index was built already
+    fn reindex(&mut self, point: Point2D, old: &T, new: &T){
+        if old == new {
+            return;
+        }
+        if let Some(index) = self.index.get_mut() {
+            if let Some(points) = index.get_mut(old) {
+                points.remove(&point);
+                if points.is_empty() {
+                    index.remove(old);
+                }
+            }
+            index.entry(new.clone()).or_default().insert(point);
+        }
+    }
+
+    //writes value at point and returns what was there before
+    pub(crate) fn set(&mut self, point: &Point2D, value: T) -> T{
+        let offset = self.offset_or_panic(point);
+        let old = self.cells[offset].clone();
+        self.reindex(*point, &old, &value);
+        std::mem::replace(&mut self.cells[offset], value)
+    }
+
+    pub(crate) fn swap(&mut self, a: &Point2D, b: &Point2D){
+        let (offset_a, offset_b) = (self.offset_or_panic(a), self.offset_or_panic(b));
+        let (value_a, value_b) = (self.cells[offset_a].clone(), self.cells[offset_b].clone());
+        self.reindex(*a, &value_a, &value_b);
+        self.reindex(*b, &value_b, &value_a);
+        self.cells.swap(offset_a, offset_b);
+    }
+
+    //carries the value at from over to, leaves fill behind and returns the value it overwrote
+    pub(crate) fn move_element(&mut self, from: &Point2D, to: &Point2D, fill: T) -> T{
+        let value = self.set(from, fill);
+        self.set(to, value)
+    }
+
+    //writes all changes in order, a later change to the same point wins
+    pub(crate) fn update<I: IntoIterator<Item = (Point2D, T)>>(&mut self, changes: I){
+        for (point, value) in changes {
+            self.set(&point, value);
+        }
+    }
+
+    //moves every cell of row y by shift to the right, cells pushed off the edge come back on the other side
+    pub(crate) fn rotate_row(&mut self, y: usize, shift: isize){
+        self.rotate_line((0..self.width).map(|x| Point2D::new(x, y)).collect(), shift);
+    }
+
+    //moves every cell of column x by shift towards growing y, wrapping around like rotate_row
+    pub(crate) fn rotate_column(&mut self, x: usize, shift: isize){
+        self.rotate_line((0..self.height).map(|y| Point2D::new(x, y)).collect(), shift);
+    }
+
+    fn rotate_line(&mut self, line: Vec<Point2D>, shift: isize){
+        assert_eq!(self.topology, Topology::Toroidal, "Only toroidal grids can be rotated");
+        let n = line.len() as isize;
+        let changes: Vec<(Point2D, T)> = line.iter()
+            .enumerate()
+            .map(|(i, p)| (line[(i as isize + shift).rem_euclid(n) as usize], self[p].clone()))
+            .collect();
+        self.update(changes);
+    }
+
+    pub(crate) fn snapshot(&self) -> GridSnapshot<T>{
+        GridSnapshot{cells: self.cells.clone(), index: self.index.get().cloned()}
+    }
+
+    //puts back the cells and the index of a snapshot taken from this grid
+    pub(crate) fn restore(&mut self, snapshot: GridSnapshot<T>){
+        assert_eq!(snapshot.cells.len(), self.cells.len(), "Snapshot of a grid of another size");
+        self.cells = snapshot.cells;
+        self.index = match snapshot.index {
+            Some(index) => OnceLock::from(index),
+            None => OnceLock::new()
+        };
+    }
+}
+
+//one line per row, the cells written next to each other
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for value in row {
+                write!(f, "{}", value)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+//grids are equal when their shapes and cells are, the index does not matter
+impl<T: PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.width, self.height, self.orientation, self.topology) == (other.width, other.height, other.orientation, other.topology)
+            && self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for Grid<T> {}
+
+impl<T: Hash> Hash for Grid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.orientation.hash(state);
+        self.topology.hash(state);
+        self.cells.hash(state);
+    }
+}
+
+impl<T> Index<&Point2D> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: &Point2D) -> &T {
+        &self.cells[self.offset_or_panic(point)]
+    }
+}
+
+//writing through a mutable reference bypasses the index, so it is dropped and rebuilt when needed
+impl<T> IndexMut<&Point2D> for Grid<T> {
+    fn index_mut(&mut self, point: &Point2D) -> &mut T {
+        let offset = self.offset_or_panic(point);
+        self.index.take();
+        &mut self.cells[offset]
+    }
+}
+
+impl<T> Index<Point2D> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2D) -> &T {
+        &self[&point]
+    }
+}
+
+impl<T> IndexMut<Point2D> for Grid<T> {
+    fn index_mut(&mut self, point: Point2D) -> &mut T {
+        &mut self[&point]
+    }
+}
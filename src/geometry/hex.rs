@@ -0,0 +1,316 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+use crate::geometry::point::Point3D;
+
+//axial coordinates of a hexagonal cell, the third cube coordinate is s = -q - r
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Hex {
+    q: isize,
+    r: isize
+}
+
+//the six axial unit offsets going counterclockwise, starting east of the pointy-top layout
+const AXIAL_OFFSETS: [(isize, isize); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+#[allow(dead_code)]
+impl Hex {
+    pub(crate) fn new(q: isize, r: isize) -> Self{
+        Self{q, r}
+    }
+
+    pub(crate) fn origin() -> Self{
+        Self{q: 0, r: 0}
+    }
+
+    pub(crate) fn from_cube(q: isize, r: isize, s: isize) -> Self{
+        assert_eq!(q + r + s, 0, "Cube coordinates must sum to zero");
+        Self{q, r}
+    }
+
+    pub(crate) fn q(&self) -> isize{
+        self.q
+    }
+
+    pub(crate) fn r(&self) -> isize{
+        self.r
+    }
+
+    pub(crate) fn s(&self) -> isize{
+        -self.q - self.r
+    }
+
+    pub(crate) fn to_cube(self) -> Point3D{
+        Point3D::new(self.q, self.r, self.s())
+    }
+
+    pub(crate) fn unit_offsets() -> [Hex; 6]{
+        AXIAL_OFFSETS.map(|(q, r)| Hex::new(q, r))
+    }
+
+    pub(crate) fn neighbors(&self) -> [Hex; 6]{
+        Self::unit_offsets().map(|d| *self + d)
+    }
+
+    //number of steps from the origin
+    pub(crate) fn length(&self) -> usize{
+        (self.q.unsigned_abs() + self.r.unsigned_abs() + self.s().unsigned_abs()) / 2
+    }
+
+    pub(crate) fn distance(&self, other: &Hex) -> usize{
+        (*self - *other).length()
+    }
+
+    pub(crate) fn ring(&self, radius: usize) -> Vec<Hex>{
+        if radius == 0 {
+            return vec![*self];
+        }
+        let offsets = Self::unit_offsets();
+        //start at the corner reached by walking south-west, then walk radius steps along each side
+        let mut h = *self + offsets[4] * radius as isize;
+        let mut ring = Vec::with_capacity(6 * radius);
+        for side in offsets {
+            for _ in 0..radius {
+                ring.push(h);
+                h = h + side;
+            }
+        }
+        ring
+    }
+
+    //center first, then rings of growing radius
+    pub(crate) fn spiral(&self, radius: usize) -> Vec<Hex>{
+        (0..=radius).flat_map(|k| self.ring(k)).collect()
+    }
+
+    //every cell crossed by the straight segment between the two cell centers
+    pub(crate) fn line(&self, other: &Hex) -> Vec<Hex>{
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![*self];
+        }
+        //nudge the end points so segments running along cell edges round consistently
+        let (aq, ar, as_) = (self.q as f64 + 1e-6, self.r as f64 + 1e-6, self.s() as f64 - 2e-6);
+        let (bq, br, bs) = (other.q as f64 + 1e-6, other.r as f64 + 1e-6, other.s() as f64 - 2e-6);
+        (0..=n).map(|i| {
+            let t = i as f64 / n as f64;
+            Hex::round(aq + (bq - aq) * t, ar + (br - ar) * t, as_ + (bs - as_) * t)
+        }).collect()
+    }
+
+    fn round(q: f64, r: f64, s: f64) -> Hex{
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Hex::new(rq as isize, rr as isize)
+    }
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.q, self.r, self.s())
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Mul<isize> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: isize) -> Hex {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Sum for Hex {
+    fn sum<I: Iterator<Item = Hex>>(iter: I) -> Hex {
+        iter.fold(Hex::origin(), |acc, h| acc + h)
+    }
+}
+
+impl<'a> Sum<&'a Hex> for Hex {
+    fn sum<I: Iterator<Item = &'a Hex>>(iter: I) -> Hex {
+        iter.fold(Hex::origin(), |acc, h| acc + *h)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum HexDirection {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest
+}
+
+impl FromStr for HexDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "n" => Ok(HexDirection::North),
+            "ne" => Ok(HexDirection::NorthEast),
+            "e" => Ok(HexDirection::East),
+            "se" => Ok(HexDirection::SouthEast),
+            "s" => Ok(HexDirection::South),
+            "sw" => Ok(HexDirection::SouthWest),
+            "w" => Ok(HexDirection::West),
+            "nw" => Ok(HexDirection::NorthWest),
+            _ => Err(format!("Unknown hex direction {}", s))
+        }
+    }
+}
+
+//flat-top cells have neighbours n/ne/se/s/sw/nw, pointy-top cells have ne/e/se/sw/w/nw
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HexLayout {
+    FlatTop,
+    PointyTop
+}
+
+#[allow(dead_code)]
+impl HexLayout {
+    pub(crate) fn directions(&self) -> [HexDirection; 6]{
+        match self {
+            HexLayout::FlatTop => [HexDirection::North, HexDirection::NorthEast, HexDirection::SouthEast,
+                HexDirection::South, HexDirection::SouthWest, HexDirection::NorthWest],
+            HexLayout::PointyTop => [HexDirection::NorthEast, HexDirection::East, HexDirection::SouthEast,
+                HexDirection::SouthWest, HexDirection::West, HexDirection::NorthWest]
+        }
+    }
+
+    //None for the two directions which point at a vertex rather than a neighbour in this layout
+    pub(crate) fn offset(&self, direction: HexDirection) -> Option<Hex>{
+        let (q, r) = match (self, direction) {
+            (HexLayout::FlatTop, HexDirection::North) => (0, -1),
+            (HexLayout::FlatTop, HexDirection::NorthEast) => (1, -1),
+            (HexLayout::FlatTop, HexDirection::SouthEast) => (1, 0),
+            (HexLayout::FlatTop, HexDirection::South) => (0, 1),
+            (HexLayout::FlatTop, HexDirection::SouthWest) => (-1, 1),
+            (HexLayout::FlatTop, HexDirection::NorthWest) => (-1, 0),
+            (HexLayout::PointyTop, HexDirection::NorthEast) => (1, -1),
+            (HexLayout::PointyTop, HexDirection::East) => (1, 0),
+            (HexLayout::PointyTop, HexDirection::SouthEast) => (0, 1),
+            (HexLayout::PointyTop, HexDirection::SouthWest) => (-1, 1),
+            (HexLayout::PointyTop, HexDirection::West) => (-1, 0),
+            (HexLayout::PointyTop, HexDirection::NorthWest) => (0, -1),
+            _ => return None
+        };
+        Some(Hex::new(q, r))
+    }
+
+    pub(crate) fn step(&self, direction: &str) -> Option<Hex>{
+        self.offset(direction.parse::<HexDirection>().ok()?)
+    }
+
+    pub(crate) fn neighbor(&self, h: &Hex, direction: HexDirection) -> Option<Hex>{
+        Some(*h + self.offset(direction)?)
+    }
+}
+
+//text drawing of a hex map, every other character position belongs to a cell:
+//pointy-top rows are shifted by one column, flat-top columns are shifted by one row
+//
+//  pointy-top      flat-top
+//   a b c          a.c.
+//  d e f           .b.d
+//   g h i          e.f.
+#[derive(Debug, Clone)]
+pub(crate) struct HexCanvas {
+    layout: HexLayout,
+    cells: HashMap<Hex, char>,
+    elements: BTreeMap<char, BTreeSet<Hex>>,
+    n_rows: usize,
+    parity: Option<usize>
+}
+
+#[allow(dead_code)]
+impl HexCanvas {
+    pub(crate) fn new(layout: HexLayout) -> Self{
+        Self{
+            layout,
+            cells: HashMap::new(),
+            elements: BTreeMap::new(),
+            n_rows: 0,
+            parity: None
+        }
+    }
+
+    pub(crate) fn layout(&self) -> HexLayout{
+        self.layout
+    }
+
+    pub(crate) fn add_row(&mut self, row: &str){
+        let row_index = self.n_rows;
+        self.n_rows += 1;
+        for (col, ch) in row.chars().enumerate() {
+            if ch.is_whitespace() {
+                continue;
+            }
+            //the first cell fixes which character positions are cells
+            let parity = *self.parity.get_or_insert((col + row_index) % 2);
+            if (col + row_index) % 2 != parity {
+                continue;
+            }
+            let (col, row) = (col as isize, row_index as isize);
+            let shift = parity as isize;
+            let h = match self.layout {
+                HexLayout::PointyTop => Hex::new((col - row - shift).div_euclid(2), row),
+                HexLayout::FlatTop => Hex::new(col, (row - col - shift).div_euclid(2))
+            };
+            self.cells.insert(h, ch);
+            self.elements.entry(ch).or_default().insert(h);
+        }
+    }
+
+    pub(crate) fn get_element(&self, h: &Hex) -> Option<&char>{
+        self.cells.get(h)
+    }
+
+    pub(crate) fn elements(&self) -> &BTreeMap<char, BTreeSet<Hex>>{
+        &self.elements
+    }
+
+    pub(crate) fn try_locate_element(&self, el: &char) -> Result<&BTreeSet<Hex>, String>{
+        match self.elements.get(el){
+            None => Err(format!("Cannot locate {}", el)),
+            Some(locations) => Ok(locations)
+        }
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Hex, &char)>{
+        self.cells.iter()
+    }
+
+    pub(crate) fn len(&self) -> usize{
+        self.cells.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool{
+        self.cells.is_empty()
+    }
+}
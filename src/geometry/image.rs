@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use crate::geometry::grid::Grid;
+use crate::geometry::point::Point2D;
+use crate::geometry::rect::Rect;
+use crate::geometry::Orientation;
+
+pub(crate) type Rgb = [u8; 3];
+
+pub(crate) const BLACK: Rgb = [0, 0, 0];
+pub(crate) const WHITE: Rgb = [255, 255, 255];
+
+//colors for the values of a grid, values without one of their own get the default
+#[derive(Debug, Clone)]
+pub(crate) struct Palette<T> {
+    colors: BTreeMap<T, Rgb>,
+    default: Rgb
+}
+
+#[allow(dead_code)]
+impl<T: Ord> Palette<T> {
+    pub(crate) fn new(default: Rgb) -> Self{
+        Self{colors: BTreeMap::new(), default}
+    }
+
+    pub(crate) fn with(mut self, value: T, color: Rgb) -> Self{
+        self.colors.insert(value, color);
+        self
+    }
+
+    pub(crate) fn color(&self, value: &T) -> Rgb{
+        self.colors.get(value).copied().unwrap_or(self.default)
+    }
+}
+
+//raster picture with one pixel per cell, saved as binary PPM or as PNG depending on the file extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Image {
+    width: usize,
+    height: usize,
+    origin: Point2D,
+    pixels: Vec<Rgb>
+}
+
+#[allow(dead_code)]
+impl Image {
+    //area filled with the background color, the min corner of bounds becomes the top left pixel
+    pub(crate) fn blank(bounds: Rect, background: Rgb) -> Self{
+        let (width, height) = (bounds.extent(0), bounds.extent(1));
+        Self{width, height, origin: *bounds.min_corner(), pixels: vec![background; width * height]}
+    }
+
+    //in Math orientation the row with the largest y becomes the top one, as in Render
+    pub(crate) fn from_grid<T>(grid: &Grid<T>, color: impl Fn(&T) -> Rgb) -> Self{
+        let rows: Vec<&[T]> = match grid.orientation() {
+            Orientation::Screen => grid.rows().collect(),
+            Orientation::Math => (0..grid.height()).rev().map(|y| grid.row(y)).collect()
+        };
+        Self{
+            width: grid.width(),
+            height: grid.height(),
+            origin: Point2D::origin(),
+            pixels: rows.into_iter().flatten().map(color).collect()
+        }
+    }
+
+    //the points in foreground on background, framed by their bounding box
+    pub(crate) fn from_points<'a, I>(points: I, foreground: Rgb, background: Rgb) -> Self
+    where
+        I: IntoIterator<Item = &'a Point2D>,
+    {
+        let points: Vec<&Point2D> = points.into_iter().collect();
+        match Rect::bounding(points.iter().copied()) {
+            Some(bounds) => Self::blank(bounds, background).plot(points, foreground),
+            None => Self{width: 0, height: 0, origin: Point2D::origin(), pixels: Vec::new()}
+        }
+    }
+
+    pub(crate) fn shape(&self) -> (usize, usize){
+        (self.width, self.height)
+    }
+
+    //row by row from the top left
+    pub(crate) fn pixels(&self) -> impl Iterator<Item = &Rgb>{
+        self.pixels.iter()
+    }
+
+    fn offset(&self, p: &Point2D) -> Option<usize>{
+        let local = *p - self.origin;
+        if local.is_out_of_bounds(self.width, self.height) {
+            None
+        } else {
+            Some(*local.y() as usize * self.width + *local.x() as usize)
+        }
+    }
+
+    pub(crate) fn get_pixel(&self, p: &Point2D) -> Option<Rgb>{
+        self.offset(p).map(|offset| self.pixels[offset])
+    }
+
+    //points outside of the picture are ignored
+    pub(crate) fn set_pixel(&mut self, p: &Point2D, color: Rgb){
+        if let Some(offset) = self.offset(p) {
+            self.pixels[offset] = color;
+        }
+    }
+
+    pub(crate) fn plot<'a, I>(mut self, points: I, color: Rgb) -> Self
+    where
+        I: IntoIterator<Item = &'a Point2D>,
+    {
+        for p in points {
+            self.set_pixel(p, color);
+        }
+        self
+    }
+
+    //every pixel blown up to a factor x factor square
+    pub(crate) fn scaled(&self, factor: usize) -> Self{
+        let (width, height) = (self.width * factor, self.height * factor);
+        let pixels = (0..width * height)
+            .map(|offset| self.pixels[(offset / width / factor) * self.width + (offset % width) / factor])
+            .collect();
+        Self{width, height, origin: self.origin, pixels}
+    }
+
+    //binary PPM (P6)
+    pub(crate) fn to_pnm(&self) -> Vec<u8>{
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flatten());
+        bytes
+    }
+
+    //truecolor PNG; the image data goes into stored deflate blocks, so nothing is compressed
+    //but no codec is needed either
+    pub(crate) fn to_png(&self) -> Vec<u8>{
+        let mut raw = Vec::with_capacity(self.height * (3 * self.width + 1));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            raw.push(0);
+            raw.extend(row.iter().flatten());
+        }
+        let mut ihdr = Vec::new();
+        ihdr.extend((self.width as u32).to_be_bytes());
+        ihdr.extend((self.height as u32).to_be_bytes());
+        ihdr.extend([8, 2, 0, 0, 0]);
+
+        let mut bytes = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        png_chunk(&mut bytes, b"IHDR", &ihdr);
+        png_chunk(&mut bytes, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut bytes, b"IEND", &[]);
+        bytes
+    }
+
+    //.png files are written as PNG, anything else as PPM
+    pub(crate) fn write_to_file(&self, filename: &str) -> io::Result<()>{
+        if filename.ends_with(".png") {
+            fs::write(filename, self.to_png())
+        } else {
+            fs::write(filename, self.to_pnm())
+        }
+    }
+}
+
+fn png_chunk(bytes: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]){
+    bytes.extend((data.len() as u32).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend(kind);
+    bytes.extend(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend(crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32{
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32{
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+//zlib stream made of uncompressed deflate blocks of at most 65535 bytes
+fn zlib_stored(data: &[u8]) -> Vec<u8>{
+    let mut bytes = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if data.is_empty() { vec![&[]] } else { data.chunks(65535).collect() };
+    for (i, block) in blocks.iter().enumerate() {
+        bytes.push(u8::from(i + 1 == blocks.len()));
+        let len = block.len() as u16;
+        bytes.extend(len.to_le_bytes());
+        bytes.extend((!len).to_le_bytes());
+        bytes.extend(*block);
+    }
+    bytes.extend(adler32(data).to_be_bytes());
+    bytes
+}
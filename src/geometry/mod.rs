@@ -0,0 +1,399 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Mul};
+use std::rc::Rc;
+
+mod point;
+mod hex;
+mod rect;
+mod polygon;
+mod curve;
+mod grid;
+mod sparse;
+mod region;
+mod symmetry;
+mod topology;
+mod render;
+mod image;
+mod recorder;
+mod automaton;
+
+#[allow(unused_imports)]
+pub(crate) use point::{Point, Point2D, Point3D, Point4D};
+#[allow(unused_imports)]
+pub(crate) use hex::{Hex, HexCanvas, HexDirection, HexLayout};
+#[allow(unused_imports)]
+pub(crate) use rect::{disjoint_union, subtract_all, union_volume, Cuboid, Rect};
+pub(crate) use polygon::Polygon;
+#[allow(unused_imports)]
+pub(crate) use grid::{Canvas, Grid, GridSnapshot};
+#[allow(unused_imports)]
+pub(crate) use sparse::{GridLike, SparseGrid};
+#[allow(unused_imports)]
+pub(crate) use region::{flood_fill, Connectivity, Labelling, Region};
+#[allow(unused_imports)]
+pub(crate) use symmetry::{GridView, Symmetry};
+#[allow(unused_imports)]
+pub(crate) use topology::Topology;
+#[allow(unused_imports)]
+pub(crate) use curve::{CantorDiagonal, Curve, HilbertCurve, SquareSpiral, ZOrderCurve};
+#[allow(unused_imports)]
+pub(crate) use render::{Color, Render};
+#[allow(unused_imports)]
+pub(crate) use image::{Image, Palette, Rgb, BLACK, WHITE};
+#[allow(unused_imports)]
+pub(crate) use recorder::{set_recording, FrameFormat, RecordOptions, Recorder};
+#[allow(unused_imports)]
+pub(crate) use automaton::{Automaton, Boundary, Rule};
+
+//Screen coordinates grow to the right and downward like canvas rows and columns,
+//Math coordinates grow to the right and upward. Direction names always describe the picture,
+//so Up is (0, -1) on screen and (0, 1) in math coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub(crate) enum Orientation {
+    #[default]
+    Screen,
+    Math
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Direction{
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+    None,
+    ToPoint(Point2D)
+}
+
+impl Direction {
+    pub(crate) fn mirror(self) -> Self{
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::Right => Direction::Left,
+            Direction::DownRight => Direction::UpLeft,
+            Direction::Down => Direction::Up,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::Left => Direction::Right,
+            Direction::UpLeft => Direction::DownRight,
+            Direction::None => Direction::None,
+            Direction::ToPoint(p) => Direction::ToPoint(-p)
+        }
+    }
+
+    //compass directions ordered clockwise starting at Up, 45 degrees apart
+    const COMPASS: [Direction; 8] = [Direction::Up, Direction::UpRight, Direction::Right, Direction::DownRight,
+        Direction::Down, Direction::DownLeft, Direction::Left, Direction::UpLeft];
+
+    //position in COMPASS, also for a ToPoint holding one of the eight unit offsets
+    pub(crate) fn index(self) -> Option<usize>{
+        let t = self.to_tuple();
+        Direction::COMPASS.iter().position(|d| d.to_tuple() == t)
+    }
+
+    pub(crate) fn from_index(index: isize) -> Self{
+        Direction::COMPASS[index.rem_euclid(8) as usize]
+    }
+
+    //compass bearing in degrees, clockwise from Up
+    #[allow(dead_code)]
+    pub(crate) fn angle(self) -> Option<u16>{
+        self.index().map(|i| i as u16 * 45)
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn from_angle(degrees: i32) -> Option<Self>{
+        if degrees % 45 == 0 {
+            Some(Direction::from_index((degrees / 45) as isize))
+        } else {
+            None
+        }
+    }
+
+    //steps of 45 degrees clockwise; offsets other than the eight unit ones can only be rotated
+    //by multiples of 90 degrees, None otherwise
+    fn rotate(self, steps: isize) -> Option<Self>{
+        if let Direction::None = self {
+            return Some(Direction::None);
+        }
+        if let Some(i) = self.index() {
+            return Some(Direction::from_index(i as isize + steps));
+        }
+        let (x, y) = self.to_tuple();
+        let (x, y) = match steps.rem_euclid(8) {
+            0 => (x, y),
+            2 => (-y, x),
+            4 => (-x, -y),
+            6 => (y, -x),
+            _ => return None
+        };
+        Some(Direction::ToPoint(Point2D::new(x, y)))
+    }
+
+    pub(crate) fn turn_right(self) -> Self{
+        self.rotate(2).unwrap()
+    }
+
+    pub(crate) fn turn_left(self) -> Self{
+        self.rotate(-2).unwrap()
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn turn_right_45(self) -> Option<Self>{
+        self.rotate(1)
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn turn_left_45(self) -> Option<Self>{
+        self.rotate(-1)
+    }
+
+    pub(crate) fn reverse(self) -> Self{
+        self.mirror()
+    }
+
+    pub(crate) fn apply(self, turn: Turn) -> Self{
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+            Turn::Straight => self,
+            Turn::Back => self.reverse()
+        }
+    }
+
+    //arrows (^v<>), letters (UDLR) and compass points (NESW)
+    pub(crate) fn try_from_char(ch: &char) -> Option<Self>{
+        match ch {
+            '^' | 'U' | 'N' => Some(Direction::Up),
+            'v' | 'D' | 'S' => Some(Direction::Down),
+            '>' | 'R' | 'E' => Some(Direction::Right),
+            '<' | 'L' | 'W' => Some(Direction::Left),
+            _ => None
+        }
+    }
+
+    //arrow of a base direction, None for the others
+    pub(crate) fn to_char(self) -> Option<char>{
+        match self {
+            Direction::Up => Some('^'),
+            Direction::Right => Some('>'),
+            Direction::Down => Some('v'),
+            Direction::Left => Some('<'),
+            _ => None
+        }
+    }
+
+    //offset in the given orientation, ToPoint offsets are taken as they are
+    pub(crate) fn to_tuple_in(self, orientation: Orientation) -> (isize, isize) {
+        let (dx, dy) = match self {
+            Direction::Up => (0, -1),
+            Direction::UpRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (0, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, -1),
+            Direction::None => (0, 0),
+            Direction::ToPoint(p) => return (*p.x(), *p.y())
+        };
+        match orientation {
+            Orientation::Screen => (dx, dy),
+            Orientation::Math => (dx, -dy)
+        }
+    }
+
+    pub(crate) fn to_tuple(self) -> (isize, isize) {
+        self.to_tuple_in(Orientation::Screen)
+    }
+
+    pub(crate) fn to_point_in(self, orientation: Orientation) -> Point2D{
+        let (dx, dy) = self.to_tuple_in(orientation);
+        Point2D::new(dx, dy)
+    }
+
+    pub(crate) fn to_point(self) -> Point2D{
+        self.to_point_in(Orientation::Screen)
+    }
+
+    pub(crate) fn diagonal() -> [Direction; 4]{
+        [Direction::UpRight, Direction::DownRight,
+            Direction::DownLeft, Direction::UpLeft]
+    }
+
+    pub(crate) fn base() -> [Direction; 4]{
+        [Direction::Up,  Direction::Right, Direction::Down, Direction::Left]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Turn {
+    Left,
+    Right,
+    Straight,
+    Back
+}
+
+impl Turn {
+    pub(crate) fn try_from_char(ch: &char) -> Option<Self>{
+        match ch {
+            'L' => Some(Turn::Left),
+            'R' => Some(Turn::Right),
+            'S' | 'F' => Some(Turn::Straight),
+            'B' => Some(Turn::Back),
+            _ => None
+        }
+    }
+}
+
+impl Hash for Direction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let p = self.to_point();
+        p.hash(state);
+    }
+}
+
+impl Eq for Direction {}
+
+
+impl Add<&Direction> for &Point2D {
+    type Output = Point2D;
+
+    fn add(self, other: &Direction) -> Point2D {
+        *self + other.to_point()
+    }
+}
+
+impl Add<&Direction> for &Rc<Point2D> {
+    type Output = Rc<Point2D>;
+
+    fn add(self, other: &Direction) -> Rc<Point2D> {
+        let p = other.to_point();
+        Rc::new(**self + p)
+    }
+}
+
+impl Add<&Direction> for &Direction {
+    type Output = Direction;
+
+    fn add(self, other: &Direction) -> Direction {
+        let p1 = other.to_point();
+        let p0 = self.to_point();
+        Direction::ToPoint(p0 + p1)
+    }
+}
+
+impl<T> Mul<T> for &Direction
+where
+    T: Copy + TryInto<isize>,
+    <T as TryInto<isize>>::Error: std::fmt::Debug,
+{
+    type Output = Direction;
+
+    fn mul(self, rhs: T) -> Direction {
+        let rhs = rhs.try_into().expect("Conversion failed");
+        let p = self.to_point();
+        Direction::ToPoint(p * rhs)
+    }
+}
+
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Vector {
+    direction: Direction,
+    anchor: Point2D
+}
+
+impl Vector {
+    pub(crate) fn new(direction: Direction, anchor: Point2D) -> Self{
+        Self{
+            direction,
+            anchor
+        }
+    }
+    pub(crate) fn null() -> Self{
+        Self{
+            direction: Direction::None,
+            anchor: Point2D::new(0, 0)
+        }
+    }
+
+    pub(crate) fn direction(&self) -> &Direction{
+        &self.direction
+    }
+    pub(crate) fn anchor(&self) -> &Point2D{
+        &self.anchor
+    }
+
+    pub(crate) fn is_out_of_bounds(&self, length: usize, width: usize, height: usize) -> bool {
+        //either start or end of vector is out of bounds
+        self.anchor.is_out_of_bounds(width, height) | self.get_point(length-1).is_out_of_bounds(width, height)
+    }
+    pub(crate) fn get_point(&self, length: usize) -> Point2D{
+        &self.anchor + &(&self.direction *length)
+    }
+
+    pub(crate) fn shift(&self, d: &Direction) -> Vector {
+        Vector::new(self.direction, &self.anchor + d)
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn change_anchor(&mut self, anchor: Point2D){
+        self.anchor = anchor;
+    }
+
+    pub(crate) fn change_direction(&mut self, direction: Direction){
+        self.direction = direction;
+    }
+}
+
+impl Eq for Vector {}
+
+impl Hash for Vector {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.direction.to_point().hash(state);
+        self.anchor.hash(state);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ScoredPosition{
+    id: usize,
+    pub(crate) score: usize,
+    pub(crate) direction: Direction,
+    pub(crate) location: Rc<Point2D>,
+    pub(crate) path: HashSet<Rc<Point2D>>
+}
+
+impl ScoredPosition{
+    pub(crate) fn new(id:usize, score: usize, direction: Direction, location: Rc<Point2D>, path: HashSet<Rc<Point2D>>)->Self{
+        Self{
+            id,
+            score,
+            direction,
+            location,
+            path
+        }
+    }
+}
+
+impl Eq for ScoredPosition {}
+
+impl PartialOrd<Self> for ScoredPosition {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredPosition{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score.cmp(&other.score).then_with(|| self.id.cmp(&other.id))
+    }
+}
+//.then_with(|| self.path.len().cmp(&other.path.len()))
\ No newline at end of file
@@ -0,0 +1,223 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use crate::geometry::Direction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Point<const N: usize> {
+    coords: [isize; N]
+}
+
+pub(crate) type Point2D = Point<2>;
+#[allow(dead_code)]
+pub(crate) type Point3D = Point<3>;
+#[allow(dead_code)]
+pub(crate) type Point4D = Point<4>;
+
+#[allow(dead_code)]
+impl<const N: usize> Point<N> {
+    pub(crate) fn from_coords(coords: [isize; N]) -> Self{
+        Self{coords}
+    }
+
+    pub(crate) fn origin() -> Self{
+        Self{coords: [0; N]}
+    }
+
+    pub(crate) fn coords(&self) -> &[isize; N]{
+        &self.coords
+    }
+
+    pub(crate) fn coord(&self, axis: usize) -> isize{
+        self.coords[axis]
+    }
+
+    pub(crate) fn set_coord(&mut self, axis: usize, value: isize){
+        self.coords[axis] = value;
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(isize, isize) -> isize) -> Self{
+        Self{coords: std::array::from_fn(|i| f(self.coords[i], other.coords[i]))}
+    }
+
+    pub(crate) fn manhattan(&self) -> usize{
+        self.coords.iter().map(|c| c.unsigned_abs()).sum()
+    }
+
+    pub(crate) fn chebyshev(&self) -> usize{
+        self.coords.iter().map(|c| c.unsigned_abs()).max().unwrap_or(0)
+    }
+
+    pub(crate) fn manhattan_distance(&self, other: &Self) -> usize{
+        (*self - *other).manhattan()
+    }
+
+    pub(crate) fn chebyshev_distance(&self, other: &Self) -> usize{
+        (*self - *other).chebyshev()
+    }
+
+    pub(crate) fn component_min(&self, other: &Self) -> Self{
+        self.zip_with(other, isize::min)
+    }
+
+    pub(crate) fn component_max(&self, other: &Self) -> Self{
+        self.zip_with(other, isize::max)
+    }
+
+    //unit steps along every axis, 2N of them
+    pub(crate) fn orthogonal_offsets() -> impl Iterator<Item = Self>{
+        (0..N).flat_map(|axis| [1, -1].into_iter().map(move |step| {
+            let mut p = Self::origin();
+            p.coords[axis] = step;
+            p
+        }))
+    }
+
+    //every offset in {-1, 0, 1}^N except the origin, 3^N - 1 of them
+    pub(crate) fn all_offsets() -> impl Iterator<Item = Self>{
+        (0..3usize.pow(N as u32))
+            .map(|mut code| {
+                let mut p = Self::origin();
+                for axis in 0..N {
+                    p.coords[axis] = (code % 3) as isize - 1;
+                    code /= 3;
+                }
+                p
+            })
+            .filter(|p| *p != Self::origin())
+    }
+
+    pub(crate) fn orthogonal_neighbors(&self) -> impl Iterator<Item = Self> + '_{
+        Self::orthogonal_offsets().map(move |d| *self + d)
+    }
+
+    pub(crate) fn neighbors(&self) -> impl Iterator<Item = Self> + '_{
+        Self::all_offsets().map(move |d| *self + d)
+    }
+
+    //smallest and largest corner of the axis-aligned box containing every point
+    pub(crate) fn bounding_box<'a, I>(points: I) -> Option<(Self, Self)>
+    where
+        I: IntoIterator<Item = &'a Self>,
+    {
+        let mut itr = points.into_iter();
+        let first = *itr.next()?;
+        Some(itr.fold((first, first), |(lo, hi), p| (lo.component_min(p), hi.component_max(p))))
+    }
+}
+
+impl Point2D {
+    pub(crate) fn new<T>(x: T, y: T) -> Self
+    where
+        T: TryInto<isize>,
+    {
+        let x_converted = x.try_into().unwrap_or_else(|_| {
+            eprintln!("Failed to convert x to isize, defaulting to 0");
+            0
+        });
+
+        let y_converted = y.try_into().unwrap_or_else(|_| {
+            eprintln!("Failed to convert y to isize, defaulting to 0");
+            0
+        });
+
+        Self { coords: [x_converted, y_converted] }
+    }
+
+    pub(crate) fn is_out_of_bounds(&self, width: usize, height: usize) -> bool{
+        let [x, y] = self.coords;
+        x < 0 || y < 0 || x as usize >= width || y as usize >= height
+    }
+
+    pub(crate) fn x(&self) -> &isize{
+        &self.coords[0]
+    }
+
+    pub(crate) fn y(&self) -> &isize{
+        &self.coords[1]
+    }
+
+    pub (crate) fn  get_point(&self, direction: Direction, length: usize) -> Point2D{
+        self + &(&direction *length)
+    }
+}
+
+#[allow(dead_code)]
+impl Point3D {
+    pub(crate) fn new(x: isize, y: isize, z: isize) -> Self{
+        Self{coords: [x, y, z]}
+    }
+}
+
+#[allow(dead_code)]
+impl Point4D {
+    pub(crate) fn new(x: isize, y: isize, z: isize, w: isize) -> Self{
+        Self{coords: [x, y, z, w]}
+    }
+}
+
+impl<const N: usize> From<[isize; N]> for Point<N> {
+    fn from(coords: [isize; N]) -> Self {
+        Self{coords}
+    }
+}
+
+impl<const N: usize> fmt::Display for Point<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.coords.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Point<N>;
+
+    fn add(self, other: Point<N>) -> Point<N> {
+        self.zip_with(&other, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> Add<&Point<N>> for Point<N> {
+    type Output = Point<N>;
+
+    fn add(self, other: &Point<N>) -> Point<N> {
+        self + *other
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Point<N>;
+
+    fn sub(self, other: Point<N>) -> Point<N> {
+        self.zip_with(&other, |a, b| a - b)
+    }
+}
+
+impl Sub<&Point2D> for &Point2D {
+    type Output = Direction;
+
+    fn sub(self, other: &Point2D) -> Direction {
+        Direction::ToPoint(*self - *other)
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Point<N>;
+
+    fn neg(self) -> Point<N> {
+        Self{coords: self.coords.map(|c| -c)}
+    }
+}
+
+impl<const N: usize> Mul<isize> for Point<N> {
+    type Output = Point<N>;
+
+    fn mul(self, rhs: isize) -> Point<N> {
+        Self{coords: self.coords.map(|c| c * rhs)}
+    }
+}
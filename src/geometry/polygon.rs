@@ -0,0 +1,173 @@
+use std::collections::{BTreeMap, HashSet};
+use crate::geometry::point::Point2D;
+use crate::geometry::Direction;
+use crate::math::gcd;
+
+//closed lattice polygon, the last vertex connects back to the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Polygon {
+    vertices: Vec<Point2D>
+}
+
+#[allow(dead_code)]
+impl Polygon {
+    pub(crate) fn new(vertices: Vec<Point2D>) -> Self{
+        Self{vertices}
+    }
+
+    //polygon left behind by walking the given straight runs, e.g. a dig plan or a loop of pipes
+    pub(crate) fn from_walk<I>(start: Point2D, steps: I) -> Self
+    where
+        I: IntoIterator<Item = (Direction, usize)>,
+    {
+        let mut vertices = vec![start];
+        let mut current = start;
+        for (direction, length) in steps {
+            current = current.get_point(direction, length);
+            vertices.push(current);
+        }
+        if vertices.len() > 1 && vertices.last() == vertices.first() {
+            vertices.pop();
+        }
+        Self{vertices}
+    }
+
+    pub(crate) fn vertices(&self) -> &[Point2D]{
+        &self.vertices
+    }
+
+    pub(crate) fn edges(&self) -> impl Iterator<Item = (&Point2D, &Point2D)>{
+        self.vertices.iter().zip(self.vertices.iter().cycle().skip(1))
+    }
+
+    //shoelace formula, positive when the vertices run clockwise on screen (y pointing down)
+    pub(crate) fn signed_double_area(&self) -> isize{
+        self.edges().map(|(a, b)| a.x() * b.y() - b.x() * a.y()).sum()
+    }
+
+    pub(crate) fn double_area(&self) -> usize{
+        self.signed_double_area().unsigned_abs()
+    }
+
+    pub(crate) fn area(&self) -> f64{
+        self.double_area() as f64 / 2.0
+    }
+
+    pub(crate) fn is_clockwise(&self) -> bool{
+        self.signed_double_area() > 0
+    }
+
+    //lattice points lying on the edges
+    pub(crate) fn boundary_points(&self) -> usize{
+        self.edges().map(|(a, b)| {
+            let d = *b - *a;
+            gcd(*d.x() as i128, *d.y() as i128) as usize
+        }).sum()
+    }
+
+    //lattice points strictly inside, by Pick's theorem A = I + B/2 - 1;
+    //nothing is inside fewer than 3 vertices or a polygon folded onto a line
+    pub(crate) fn interior_points(&self) -> usize{
+        if self.vertices.len() < 3 {
+            return 0;
+        }
+        ((self.double_area() as isize + 2 - self.boundary_points() as isize) / 2).max(0) as usize
+    }
+
+    //lattice points inside or on the boundary, the cells dug out by a boundary walk
+    pub(crate) fn enclosed_points(&self) -> usize{
+        self.interior_points() + self.boundary_points()
+    }
+
+    pub(crate) fn perimeter(&self) -> f64{
+        self.edges().map(|(a, b)| {
+            let d = *b - *a;
+            ((d.x() * d.x() + d.y() * d.y()) as f64).sqrt()
+        }).sum()
+    }
+
+    //drops vertices lying on the straight line between their neighbours
+    pub(crate) fn simplify(&self) -> Self{
+        let n = self.vertices.len();
+        let vertices = (0..n)
+            .filter(|&i| {
+                let (prev, v, next) = (self.vertices[(i + n - 1) % n], self.vertices[i], self.vertices[(i + 1) % n]);
+                let (a, b) = (v - prev, next - v);
+                a.x() * b.y() - a.y() * b.x() != 0
+            })
+            .map(|i| self.vertices[i])
+            .collect();
+        Self{vertices}
+    }
+
+    //vertices where the boundary changes direction
+    pub(crate) fn corners(&self) -> usize{
+        self.simplify().vertices.len()
+    }
+
+    //a closed polygon has as many straight sides as corners
+    pub(crate) fn sides(&self) -> usize{
+        self.corners()
+    }
+
+    //boundaries of a set of unit cells, cell (x, y) covering the square from (x, y) to (x + 1, y + 1);
+    //the outer boundary of every connected piece runs clockwise on screen and holes run counterclockwise,
+    //so the signed areas add up to the number of cells. Cells touching only diagonally are kept apart.
+    pub(crate) fn trace_region(cells: &HashSet<Point2D>) -> Vec<Polygon>{
+        //unit boundary edges directed with the region on their right
+        let mut outgoing: BTreeMap<Point2D, Vec<Direction>> = BTreeMap::new();
+        for cell in cells {
+            let corner = |dx: isize, dy: isize| Point2D::new(cell.x() + dx, cell.y() + dy);
+            for (side, start) in [
+                (Direction::Right, corner(0, 0)),
+                (Direction::Down, corner(1, 0)),
+                (Direction::Left, corner(1, 1)),
+                (Direction::Up, corner(0, 1))
+            ] {
+                //the cell across the edge sits on its left
+                if !cells.contains(&(cell + &side.turn_left())) {
+                    outgoing.entry(start).or_default().push(side);
+                }
+            }
+        }
+
+        //where two pieces touch diagonally, turning right keeps following the same cell
+        let next = |options: &[Direction], heading: Direction| -> usize {
+            options.iter().position(|&d| d == heading.turn_right())
+                .or_else(|| options.iter().position(|&d| d == heading))
+                .unwrap_or(0)
+        };
+        let mut polygons = Vec::new();
+        while let Some((&start, directions)) = outgoing.iter().next() {
+            //the first edge stays in place until the walk wants to take it again
+            let first = directions[0];
+            let mut vertices = vec![start];
+            let mut position = &start + &first;
+            let mut heading = first;
+            loop {
+                let options = outgoing.get_mut(&position).unwrap();
+                let index = next(options, heading);
+                let direction = options[index];
+                if position == start && direction == first {
+                    break;
+                }
+                options.swap_remove(index);
+                if options.is_empty() {
+                    outgoing.remove(&position);
+                }
+                if direction != heading {
+                    vertices.push(position);
+                }
+                heading = direction;
+                position = &position + &direction;
+            }
+            let options = outgoing.get_mut(&start).unwrap();
+            options.retain(|&d| d != first);
+            if options.is_empty() {
+                outgoing.remove(&start);
+            }
+            polygons.push(Polygon{vertices}.simplify());
+        }
+        polygons
+    }
+}
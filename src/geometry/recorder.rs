@@ -0,0 +1,289 @@
+use std::cell::Cell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use crate::geometry::grid::Grid;
+use crate::geometry::image::{Image, Rgb};
+use crate::geometry::point::Point2D;
+use crate::geometry::rect::Rect;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FrameFormat {
+    Png,
+    Pnm,
+    Gif
+}
+
+impl FrameFormat {
+    pub(crate) fn from_name(name: &str) -> Option<Self>{
+        match name {
+            "png" => Some(FrameFormat::Png),
+            "pnm" | "ppm" => Some(FrameFormat::Pnm),
+            "gif" => Some(FrameFormat::Gif),
+            _ => None
+        }
+    }
+}
+
+//what to keep of the frames solvers hand over: every n-th step, at most limit frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RecordOptions {
+    pub(crate) format: FrameFormat,
+    pub(crate) every: usize,
+    pub(crate) limit: usize,
+    pub(crate) scale: usize
+}
+
+impl RecordOptions {
+    pub(crate) fn new(format: FrameFormat) -> Self{
+        Self{format, every: 1, limit: 1000, scale: 4}
+    }
+}
+
+thread_local! {
+    static RECORDING: Cell<Option<RecordOptions>> = const { Cell::new(None) };
+}
+
+//switches recording on for the solvers run afterwards on this thread, None switches it off
+pub(crate) fn set_recording(options: Option<RecordOptions>){
+    RECORDING.with(|r| r.set(options));
+}
+
+//collects the steps of a simulation as output/{year}/{name}/00000.png, ... or output/{year}/{name}.gif;
+//with recording switched off every call returns right away without drawing anything
+pub(crate) struct Recorder {
+    options: Option<RecordOptions>,
+    year: u16,
+    name: String,
+    step: usize,
+    n_frames: usize,
+    gif: Option<GifEncoder>
+}
+
+#[allow(dead_code)]
+impl Recorder {
+    pub(crate) fn new(year: u16, name: &str) -> Self{
+        Self{
+            options: RECORDING.with(|r| r.get()),
+            year,
+            name: name.to_owned(),
+            step: 0,
+            n_frames: 0,
+            gif: None
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool{
+        self.options.is_some()
+    }
+
+    fn directory(&self) -> String{
+        format!("output/{}/", self.year)
+    }
+
+    //one-off picture for inspection, written as output/{year}/{filename} only while recording is on
+    pub(crate) fn picture(&self, filename: &str, write: impl FnOnce(&str) -> io::Result<()>){
+        if !self.is_enabled() {
+            return;
+        }
+        let result = fs::create_dir_all(self.directory())
+            .and_then(|_| write(&format!("{}{}", self.directory(), filename)));
+        if let Err(e) = result {
+            println!("Cannot write {} of {}: {}", filename, self.name, e);
+        }
+    }
+
+    //called once per step, the frame is only drawn for the steps that are kept
+    pub(crate) fn record(&mut self, frame: impl FnOnce() -> Image){
+        let Some(options) = self.options else {
+            return;
+        };
+        let step = self.step;
+        self.step += 1;
+        if !step.is_multiple_of(options.every.max(1)) || self.n_frames >= options.limit {
+            return;
+        }
+        let image = frame().scaled(options.scale.max(1));
+        let result = match options.format {
+            FrameFormat::Gif => self.gif.get_or_insert_with(|| GifEncoder::new(&image, 10)).add(&image),
+            FrameFormat::Png | FrameFormat::Pnm => {
+                let directory = format!("{}{}/", self.directory(), self.name);
+                let extension = if options.format == FrameFormat::Png { "png" } else { "ppm" };
+                fs::create_dir_all(&directory)
+                    .and_then(|_| image.write_to_file(&format!("{}{:05}.{}", directory, self.n_frames, extension)))
+            }
+        };
+        match result {
+            Ok(()) => self.n_frames += 1,
+            Err(e) => {
+                println!("Cannot record frame {} of {}: {}", self.n_frames, self.name, e);
+                self.options = None;
+            }
+        }
+    }
+
+    pub(crate) fn record_grid<T>(&mut self, grid: &Grid<T>, color: impl Fn(&T) -> Rgb){
+        self.record(|| Image::from_grid(grid, color));
+    }
+
+    pub(crate) fn record_points<'a, I>(&mut self, points: I, bounds: Rect, foreground: Rgb, background: Rgb)
+    where
+        I: IntoIterator<Item = &'a Point2D>,
+    {
+        self.record(|| Image::blank(bounds, background).plot(points, foreground));
+    }
+
+    //writes the animation collected so far, nothing to do for single frames
+    pub(crate) fn finish(&mut self) -> io::Result<()>{
+        let Some(gif) = self.gif.take() else {
+            return Ok(());
+        };
+        fs::create_dir_all(self.directory())?;
+        fs::write(format!("{}{}.gif", self.directory(), self.name), gif.to_bytes())
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish() {
+            println!("Cannot write animation {}: {}", self.name, e);
+        }
+    }
+}
+
+//looping GIF89a with one global color table of 256 colors, delay in hundredths of a second;
+//every frame is compressed as it comes in, so only the encoded frames stay in memory
+struct GifEncoder {
+    width: usize,
+    height: usize,
+    delay: u16,
+    colors: Vec<Rgb>,
+    palette: HashMap<Rgb, u8>,
+    frames: Vec<Vec<u8>>
+}
+
+impl GifEncoder {
+    //the table is always full size, so the frames can be compressed before all colors are known
+    const MIN_CODE_SIZE: u8 = 8;
+
+    fn new(first: &Image, delay: u16) -> Self{
+        let (width, height) = first.shape();
+        Self{width, height, delay, colors: Vec::new(), palette: HashMap::new(), frames: Vec::new()}
+    }
+
+    fn add(&mut self, frame: &Image) -> io::Result<()>{
+        if frame.shape() != (self.width, self.height) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Frames of different size"));
+        }
+        let mut indices = Vec::with_capacity(self.width * self.height);
+        for &color in frame.pixels() {
+            let index = match self.palette.entry(color) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    if self.colors.len() == 256 {
+                        return Err(io::Error::new(io::ErrorKind::InvalidInput, "More than 256 colors"));
+                    }
+                    self.colors.push(color);
+                    *entry.insert(self.colors.len() as u8 - 1)
+                }
+            };
+            indices.push(index);
+        }
+        self.frames.push(lzw_compress(&indices, Self::MIN_CODE_SIZE));
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> Vec<u8>{
+        let (width, height) = ((self.width as u16).to_le_bytes(), (self.height as u16).to_le_bytes());
+        let mut colors = self.colors.clone();
+        colors.resize(1 << Self::MIN_CODE_SIZE, [0, 0, 0]);
+
+        let mut bytes = b"GIF89a".to_vec();
+        bytes.extend(width);
+        bytes.extend(height);
+        bytes.extend([0xF0 | (Self::MIN_CODE_SIZE - 1), 0, 0]);
+        bytes.extend(colors.iter().flatten());
+        //NETSCAPE2.0 extension, loop forever
+        bytes.extend([0x21, 0xFF, 0x0B]);
+        bytes.extend(b"NETSCAPE2.0");
+        bytes.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+        for frame in &self.frames {
+            bytes.extend([0x21, 0xF9, 0x04, 0x00]);
+            bytes.extend(self.delay.to_le_bytes());
+            bytes.extend([0x00, 0x00]);
+            bytes.push(0x2C);
+            bytes.extend([0, 0, 0, 0]);
+            bytes.extend(width);
+            bytes.extend(height);
+            bytes.push(0);
+            bytes.push(Self::MIN_CODE_SIZE);
+            for block in frame.chunks(255) {
+                bytes.push(block.len() as u8);
+                bytes.extend(block);
+            }
+            bytes.push(0);
+        }
+        bytes.push(0x3B);
+        bytes
+    }
+}
+
+//variable width LZW as used by GIF, codes packed least significant bit first;
+//the table is cleared once it is full
+fn lzw_compress(indices: &[u8], min_code_size: u8) -> Vec<u8>{
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut bytes = Vec::new();
+    let (mut buffer, mut n_bits) = (0u32, 0u32);
+    let mut emit = |code: u16, code_size: u32, bytes: &mut Vec<u8>| {
+        buffer |= (code as u32) << n_bits;
+        n_bits += code_size;
+        while n_bits >= 8 {
+            bytes.push(buffer as u8);
+            buffer >>= 8;
+            n_bits -= 8;
+        }
+    };
+
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_code_size as u32 + 1;
+    let mut next = end + 1;
+    emit(clear, code_size, &mut bytes);
+    let Some((&first, rest)) = indices.split_first() else {
+        emit(end, code_size, &mut bytes);
+        return bytes;
+    };
+    let mut prefix = first as u16;
+    for &index in rest {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        emit(prefix, code_size, &mut bytes);
+        if next < 4095 {
+            table.insert((prefix, index), next);
+            next += 1;
+            //the decoder adds its entry one code later, so the width grows once next passes the limit
+            if next > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            emit(clear, code_size, &mut bytes);
+            table.clear();
+            code_size = min_code_size as u32 + 1;
+            next = end + 1;
+        }
+        prefix = index as u16;
+    }
+    emit(prefix, code_size, &mut bytes);
+    //the decoder still adds an entry for the last code
+    if next >= 1 << code_size && code_size < 12 {
+        code_size += 1;
+    }
+    emit(end, code_size, &mut bytes);
+    if n_bits > 0 {
+        bytes.push(buffer as u8);
+    }
+    bytes
+}
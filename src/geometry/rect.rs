@@ -0,0 +1,192 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use crate::geometry::point::{Point, Point2D};
+
+//axis-aligned box with inclusive corners, min <= max along every axis, so it is never empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Cuboid<const N: usize> {
+    min: Point<N>,
+    max: Point<N>
+}
+
+pub(crate) type Rect = Cuboid<2>;
+
+#[allow(dead_code)]
+impl<const N: usize> Cuboid<N> {
+    //corners may be given in any order
+    pub(crate) fn new(a: Point<N>, b: Point<N>) -> Self{
+        Self{
+            min: a.component_min(&b),
+            max: a.component_max(&b)
+        }
+    }
+
+    pub(crate) fn from_point(p: Point<N>) -> Self{
+        Self{min: p, max: p}
+    }
+
+    pub(crate) fn bounding<'a, I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a Point<N>>,
+    {
+        Point::bounding_box(points).map(|(min, max)| Self{min, max})
+    }
+
+    pub(crate) fn min_corner(&self) -> &Point<N>{
+        &self.min
+    }
+
+    pub(crate) fn max_corner(&self) -> &Point<N>{
+        &self.max
+    }
+
+    pub(crate) fn range(&self, axis: usize) -> RangeInclusive<isize>{
+        self.min.coord(axis)..=self.max.coord(axis)
+    }
+
+    pub(crate) fn extent(&self, axis: usize) -> usize{
+        (self.max.coord(axis) - self.min.coord(axis)) as usize + 1
+    }
+
+    //number of lattice points inside, the area of a Rect
+    pub(crate) fn volume(&self) -> usize{
+        (0..N).map(|axis| self.extent(axis)).product()
+    }
+
+    pub(crate) fn contains(&self, p: &Point<N>) -> bool{
+        (0..N).all(|axis| self.range(axis).contains(&p.coord(axis)))
+    }
+
+    pub(crate) fn contains_box(&self, other: &Self) -> bool{
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    pub(crate) fn intersects(&self, other: &Self) -> bool{
+        (0..N).all(|axis| self.min.coord(axis) <= other.max.coord(axis) && other.min.coord(axis) <= self.max.coord(axis))
+    }
+
+    pub(crate) fn intersection(&self, other: &Self) -> Option<Self>{
+        if self.intersects(other) {
+            Some(Self{
+                min: self.min.component_max(&other.min),
+                max: self.max.component_min(&other.max)
+            })
+        } else {
+            None
+        }
+    }
+
+    //smallest box containing both
+    pub(crate) fn hull(&self, other: &Self) -> Self{
+        Self{
+            min: self.min.component_min(&other.min),
+            max: self.max.component_max(&other.max)
+        }
+    }
+
+    pub(crate) fn grow(&self, amount: isize) -> Option<Self>{
+        let offset = Point::from_coords([amount; N]);
+        let (min, max) = (self.min - offset, self.max + offset);
+        if (0..N).all(|axis| min.coord(axis) <= max.coord(axis)) {
+            Some(Self{min, max})
+        } else {
+            None
+        }
+    }
+
+    //the part of self not covered by other as at most 2N disjoint boxes:
+    //slabs below and above other are cut off axis by axis, what remains is the intersection
+    pub(crate) fn subtract(&self, other: &Self) -> Vec<Self>{
+        let Some(cut) = self.intersection(other) else {
+            return vec![*self];
+        };
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for axis in 0..N {
+            if rest.min.coord(axis) < cut.min.coord(axis) {
+                let mut piece = rest;
+                piece.max.set_coord(axis, cut.min.coord(axis) - 1);
+                pieces.push(piece);
+                rest.min.set_coord(axis, cut.min.coord(axis));
+            }
+            if rest.max.coord(axis) > cut.max.coord(axis) {
+                let mut piece = rest;
+                piece.min.set_coord(axis, cut.max.coord(axis) + 1);
+                pieces.push(piece);
+                rest.max.set_coord(axis, cut.max.coord(axis));
+            }
+        }
+        pieces
+    }
+
+    //lattice points with the last axis varying slowest, i.e. row by row for a Rect
+    pub(crate) fn points(&self) -> impl Iterator<Item = Point<N>> + '_{
+        let total = self.volume();
+        (0..total).map(move |mut index| {
+            let mut p = self.min;
+            for axis in 0..N {
+                let extent = self.extent(axis);
+                p.set_coord(axis, self.min.coord(axis) + (index % extent) as isize);
+                index /= extent;
+            }
+            p
+        })
+    }
+}
+
+#[allow(dead_code)]
+impl Rect {
+    //the cells of a width x height canvas
+    pub(crate) fn from_size(width: usize, height: usize) -> Self{
+        assert!(width > 0 && height > 0, "Rect cannot be empty");
+        Self{
+            min: Point2D::new(0, 0),
+            max: Point2D::new(width - 1, height - 1)
+        }
+    }
+
+    pub(crate) fn width(&self) -> usize{
+        self.extent(0)
+    }
+
+    pub(crate) fn height(&self) -> usize{
+        self.extent(1)
+    }
+
+    pub(crate) fn area(&self) -> usize{
+        self.volume()
+    }
+}
+
+impl<const N: usize> fmt::Display for Cuboid<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
+
+//total number of lattice points covered by a collection of possibly overlapping boxes
+#[allow(dead_code)]
+pub(crate) fn union_volume<const N: usize>(boxes: &[Cuboid<N>]) -> usize{
+    disjoint_union(boxes).iter().map(|b| b.volume()).sum()
+}
+
+//rewrites a collection of boxes as disjoint boxes covering the same points
+pub(crate) fn disjoint_union<const N: usize>(boxes: &[Cuboid<N>]) -> Vec<Cuboid<N>>{
+    let mut disjoint: Vec<Cuboid<N>> = Vec::new();
+    for b in boxes {
+        let mut pieces = vec![*b];
+        for d in disjoint.iter() {
+            pieces = pieces.iter().flat_map(|p| p.subtract(d)).collect();
+            if pieces.is_empty() {
+                break;
+            }
+        }
+        disjoint.extend(pieces);
+    }
+    disjoint
+}
+
+//removes other from every box of a disjoint collection
+pub(crate) fn subtract_all<const N: usize>(boxes: &[Cuboid<N>], other: &Cuboid<N>) -> Vec<Cuboid<N>>{
+    boxes.iter().flat_map(|b| b.subtract(other)).collect()
+}
@@ -0,0 +1,157 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use crate::geometry::point::Point2D;
+use crate::geometry::rect::Rect;
+use crate::geometry::sparse::GridLike;
+use crate::geometry::Direction;
+
+//which neighbours of a cell it is connected to
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub(crate) enum Connectivity {
+    //sides only
+    #[default]
+    Four,
+    //sides and corners
+    Eight
+}
+
+impl Connectivity {
+    pub(crate) fn directions(self) -> Vec<Direction>{
+        match self {
+            Connectivity::Four => Direction::base().to_vec(),
+            Connectivity::Eight => [Direction::base(), Direction::diagonal()].concat()
+        }
+    }
+}
+
+//connected cells of a grid; the perimeter counts the cell sides not shared with another cell of the region
+#[derive(Debug, Clone)]
+pub(crate) struct Region {
+    points: HashSet<Point2D>,
+    perimeter: usize,
+    bounds: Rect
+}
+
+#[allow(dead_code)]
+impl Region {
+    pub(crate) fn area(&self) -> usize{
+        self.points.len()
+    }
+
+    pub(crate) fn perimeter(&self) -> usize{
+        self.perimeter
+    }
+
+    pub(crate) fn bounds(&self) -> Rect{
+        self.bounds
+    }
+
+    pub(crate) fn points(&self) -> &HashSet<Point2D>{
+        &self.points
+    }
+
+    pub(crate) fn contains(&self, point: &Point2D) -> bool{
+        self.points.contains(point)
+    }
+}
+
+//cells reachable from seed, moving on to a neighbour only where joins(from, to) holds; the fill follows
+//the topology of the grid but never leaves its bounding rectangle, so it also ends on unbounded grids
+pub(crate) fn flood_fill<T, G: GridLike<T>>(grid: &G, seed: &Point2D, connectivity: Connectivity, joins: impl Fn(&T, &T) -> bool) -> Option<Region>{
+    let bounds = grid.bounding_rect()?;
+    grid.get_element(seed)?;
+    let directions = connectivity.directions();
+    let mut points = HashSet::from([*seed]);
+    let mut queue = VecDeque::from([*seed]);
+    while let Some(p) = queue.pop_front() {
+        let value = grid.get_element(&p).unwrap();
+        for (next_p, next_value) in grid.neighbors(&p, &directions) {
+            if bounds.contains(&next_p) && !points.contains(&next_p) && joins(value, next_value) {
+                points.insert(next_p);
+                queue.push_back(next_p);
+            }
+        }
+    }
+    let perimeter = points.iter()
+        .flat_map(|p| Direction::base().map(|d| grid.step(p, &d)))
+        .filter(|side| side.is_none_or(|q| !points.contains(&q)))
+        .count();
+    let bounds = Rect::bounding(points.iter()).unwrap();
+    Some(Region{points, perimeter, bounds})
+}
+
+//every component of a grid numbered from 0 in the order their first cells come row by row,
+//together with the graph of which components touch each other
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Labelling {
+    labels: HashMap<Point2D, usize>,
+    regions: Vec<Region>,
+    adjacent: BTreeMap<usize, BTreeSet<usize>>
+}
+
+#[allow(dead_code)]
+impl Labelling {
+    //cells without a key are background, neighbouring cells with equal keys belong to the same component
+    pub(crate) fn new<T, K, G>(grid: &G, connectivity: Connectivity, key: impl Fn(&T) -> Option<K>) -> Self
+    where
+        K: PartialEq,
+        G: GridLike<T>,
+    {
+        let mut labelling = Self::default();
+        let Some(bounds) = grid.bounding_rect() else {
+            return labelling;
+        };
+        for p in bounds.points() {
+            if labelling.labels.contains_key(&p) || grid.get_element(&p).and_then(&key).is_none() {
+                continue;
+            }
+            let region = flood_fill(grid, &p, connectivity, |a, b| key(b).is_some() && key(a) == key(b)).unwrap();
+            let label = labelling.regions.len();
+            labelling.labels.extend(region.points().iter().map(|&q| (q, label)));
+            labelling.regions.push(region);
+        }
+
+        let directions = connectivity.directions();
+        for (p, &label) in &labelling.labels {
+            for d in &directions {
+                let Some(other) = grid.step(p, d).and_then(|q| labelling.labels.get(&q)) else {
+                    continue;
+                };
+                if *other != label {
+                    labelling.adjacent.entry(label).or_default().insert(*other);
+                }
+            }
+        }
+        labelling
+    }
+
+    pub(crate) fn len(&self) -> usize{
+        self.regions.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool{
+        self.regions.is_empty()
+    }
+
+    pub(crate) fn label(&self, point: &Point2D) -> Option<usize>{
+        self.labels.get(point).copied()
+    }
+
+    pub(crate) fn regions(&self) -> &[Region]{
+        &self.regions
+    }
+
+    pub(crate) fn region(&self, label: usize) -> &Region{
+        &self.regions[label]
+    }
+
+    //labels of the components touching the given one
+    pub(crate) fn adjacent(&self, label: usize) -> impl Iterator<Item = usize> + '_{
+        self.adjacent.get(&label).into_iter().flatten().copied()
+    }
+
+    //region adjacency graph, every edge is listed from both ends
+    pub(crate) fn adjacency(&self) -> &BTreeMap<usize, BTreeSet<usize>>{
+        &self.adjacent
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use crate::geometry::grid::Grid;
+use crate::geometry::point::Point2D;
+use crate::geometry::rect::Rect;
+use crate::geometry::{Direction, Orientation};
+
+//terminal colors for highlighted cells
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan
+}
+
+impl Color {
+    fn ansi_code(self) -> u8{
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36
+        }
+    }
+}
+
+//text picture of a grid or of a set of points; overlays are painted in the order they are added
+//and colors only show up when ANSI output is switched on
+#[derive(Debug, Clone)]
+pub(crate) struct Render {
+    cells: HashMap<Point2D, char>,
+    colors: HashMap<Point2D, Color>,
+    bounds: Option<Rect>,
+    background: char,
+    orientation: Orientation,
+    ansi: bool
+}
+
+#[allow(dead_code)]
+impl Render {
+    //the given symbols inside bounds, blank everywhere else
+    pub(crate) fn from_cells<I>(bounds: Option<Rect>, cells: I) -> Self
+    where
+        I: IntoIterator<Item = (Point2D, char)>,
+    {
+        Self{
+            cells: cells.into_iter().collect(),
+            colors: HashMap::new(),
+            bounds,
+            background: ' ',
+            orientation: Orientation::Screen,
+            ansi: false
+        }
+    }
+
+    pub(crate) fn from_grid<T>(grid: &Grid<T>, symbol: impl Fn(&T) -> char) -> Self{
+        Self::from_cells(grid.bounds(), grid.iter().map(|(p, value)| (p, symbol(value))))
+            .orientation(grid.orientation())
+    }
+
+    pub(crate) fn from_canvas(canvas: &Grid<char>) -> Self{
+        Self::from_grid(canvas, |&ch| ch)
+    }
+
+    //empty '.' picture of the given area, to be filled with overlays
+    pub(crate) fn blank(bounds: Rect) -> Self{
+        Self{
+            cells: HashMap::new(),
+            colors: HashMap::new(),
+            bounds: Some(bounds),
+            background: '.',
+            orientation: Orientation::Screen,
+            ansi: false
+        }
+    }
+
+    //the points drawn with mark on a '.' background, framed by their bounding box
+    pub(crate) fn from_points<'a, I>(points: I, mark: char) -> Self
+    where
+        I: IntoIterator<Item = &'a Point2D>,
+    {
+        let cells: HashMap<Point2D, char> = points.into_iter().map(|&p| (p, mark)).collect();
+        Self{
+            bounds: Rect::bounding(cells.keys()),
+            cells,
+            ..Self::blank(Rect::from_point(Point2D::origin()))
+        }
+    }
+
+    pub(crate) fn background(mut self, background: char) -> Self{
+        self.background = background;
+        self
+    }
+
+    //in Math orientation the row with the largest y is printed first
+    pub(crate) fn orientation(mut self, orientation: Orientation) -> Self{
+        self.orientation = orientation;
+        self
+    }
+
+    pub(crate) fn ansi(mut self, ansi: bool) -> Self{
+        self.ansi = ansi;
+        self
+    }
+
+    //only the part inside rect is drawn
+    pub(crate) fn crop(mut self, rect: &Rect) -> Self{
+        self.bounds = self.bounds.and_then(|bounds| bounds.intersection(rect));
+        self
+    }
+
+    fn extend_bounds(&mut self, p: &Point2D){
+        let point_box = Rect::from_point(*p);
+        self.bounds = Some(self.bounds.map_or(point_box, |bounds| bounds.hull(&point_box)));
+    }
+
+    //draws ch over the points, growing the picture if needed
+    pub(crate) fn overlay<'a, I>(mut self, points: I, ch: char) -> Self
+    where
+        I: IntoIterator<Item = &'a Point2D>,
+    {
+        for p in points {
+            self.extend_bounds(p);
+            self.cells.insert(*p, ch);
+        }
+        self
+    }
+
+    pub(crate) fn highlight<'a, I>(mut self, points: I, color: Color) -> Self
+    where
+        I: IntoIterator<Item = &'a Point2D>,
+    {
+        self.colors.extend(points.into_iter().map(|&p| (p, color)));
+        self
+    }
+
+    //every point of the path but the last shows an arrow towards the next one,
+    //steps that are not to a neighbour leave the point untouched
+    pub(crate) fn path(mut self, path: &[Point2D]) -> Self{
+        for step in path.windows(2) {
+            let delta = step[1] - step[0];
+            let direction = Direction::base()
+                .into_iter()
+                .find(|d| d.to_point_in(self.orientation) == delta);
+            if let Some(arrow) = direction.and_then(|d| d.to_char()) {
+                self.extend_bounds(&step[0]);
+                self.cells.insert(step[0], arrow);
+            }
+        }
+        self
+    }
+
+    pub(crate) fn write_to_file(&self, filename: &str) -> io::Result<()>{
+        fs::write(filename, self.to_string())
+    }
+}
+
+impl fmt::Display for Render {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(bounds) = self.bounds else {
+            return Ok(());
+        };
+        let mut rows: Vec<isize> = bounds.range(1).collect();
+        if self.orientation == Orientation::Math {
+            rows.reverse();
+        }
+        for y in rows {
+            for x in bounds.range(0) {
+                let p = Point2D::new(x, y);
+                let ch = self.cells.get(&p).copied().unwrap_or(self.background);
+                match self.colors.get(&p) {
+                    Some(color) if self.ansi => write!(f, "\x1b[{}m{}\x1b[0m", color.ansi_code(), ch)?,
+                    _ => write!(f, "{}", ch)?
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::{HashMap, VecDeque};
+use crate::geometry::grid::Grid;
+use crate::geometry::point::Point2D;
+use crate::geometry::rect::Rect;
+use crate::geometry::render::Render;
+use crate::geometry::topology::Topology;
+use crate::geometry::{Direction, Orientation};
+
+//what algorithms need from a grid, whether it stores a fixed rectangle or only the cells in use
+#[allow(dead_code)]
+pub(crate) trait GridLike<T> {
+    //None outside of a bounded grid, unbounded grids hand out their default value instead
+    fn get_element(&self, point: &Point2D) -> Option<&T>;
+
+    //writes value at point and returns what was there before
+    fn set(&mut self, point: &Point2D, value: T) -> T;
+
+    //smallest rectangle holding every cell, None while there are none
+    fn bounding_rect(&self) -> Option<Rect>;
+
+    fn topology(&self) -> Topology;
+
+    fn orientation(&self) -> Orientation;
+
+    //neighbouring point in the given direction as the topology sees it, None if there is none
+    fn step(&self, point: &Point2D, direction: &Direction) -> Option<Point2D>;
+
+    //the neighbours in the given directions that have a value
+    fn neighbors(&self, point: &Point2D, directions: &[Direction]) -> Vec<(Point2D, &T)>{
+        directions.iter()
+            .filter_map(|d| self.step(point, d))
+            .filter_map(|p| self.get_element(&p).map(|value| (p, value)))
+            .collect()
+    }
+
+    //fewest orthogonal steps from start to every reachable cell over passable ones; like flood_fill the
+    //search never leaves the bounding rectangle, so it also ends on unbounded grids
+    fn distances(&self, start: &Point2D, passable: impl Fn(&T) -> bool) -> HashMap<Point2D, usize>
+    where
+        Self: Sized,
+    {
+        let Some(bounds) = self.bounding_rect() else {
+            return HashMap::new();
+        };
+        let mut distances = HashMap::from([(*start, 0)]);
+        let mut queue = VecDeque::from([*start]);
+        while let Some(p) = queue.pop_front() {
+            let distance = distances[&p];
+            for (next_p, value) in self.neighbors(&p, &Direction::base()) {
+                if bounds.contains(&next_p) && passable(value) && !distances.contains_key(&next_p) {
+                    distances.insert(next_p, distance + 1);
+                    queue.push_back(next_p);
+                }
+            }
+        }
+        distances
+    }
+
+    //cells of row y within the bounding rectangle, left to right
+    fn row_cells(&self, y: isize) -> Vec<(Point2D, &T)>{
+        self.bounding_rect().map_or(Vec::new(), |bounds| {
+            bounds.range(0)
+                .map(|x| Point2D::new(x, y))
+                .filter_map(|p| self.get_element(&p).map(|value| (p, value)))
+                .collect()
+        })
+    }
+
+    //cells of column x within the bounding rectangle, top to bottom
+    fn column_cells(&self, x: isize) -> Vec<(Point2D, &T)>{
+        self.bounding_rect().map_or(Vec::new(), |bounds| {
+            bounds.range(1)
+                .map(|y| Point2D::new(x, y))
+                .filter_map(|p| self.get_element(&p).map(|value| (p, value)))
+                .collect()
+        })
+    }
+
+    fn render(&self, symbol: impl Fn(&T) -> char) -> Render
+    where
+        Self: Sized,
+    {
+        let bounds = self.bounding_rect();
+        let cells = bounds.iter()
+            .flat_map(|bounds| bounds.points())
+            .filter_map(|p| self.get_element(&p).map(|value| (p, symbol(value))))
+            .collect::<Vec<_>>();
+        Render::from_cells(bounds, cells).orientation(self.orientation())
+    }
+}
+
+impl<T: Ord + Clone> GridLike<T> for Grid<T> {
+    fn get_element(&self, point: &Point2D) -> Option<&T>{
+        Grid::get_element(self, point)
+    }
+
+    fn set(&mut self, point: &Point2D, value: T) -> T{
+        Grid::set(self, point, value)
+    }
+
+    fn bounding_rect(&self) -> Option<Rect>{
+        self.bounds()
+    }
+
+    fn topology(&self) -> Topology{
+        Grid::topology(self)
+    }
+
+    fn orientation(&self) -> Orientation{
+        Grid::orientation(self)
+    }
+
+    fn step(&self, point: &Point2D, direction: &Direction) -> Option<Point2D>{
+        Grid::step(self, point, direction)
+    }
+}
+
+//unbounded grid storing only the cells written to, every other point holds the default value;
+//the bounds grow with every write and do not shrink when cells are removed
+#[derive(Debug, Clone)]
+pub(crate) struct SparseGrid<T> {
+    cells: HashMap<Point2D, T>,
+    default: T,
+    bounds: Option<Rect>,
+    orientation: Orientation
+}
+
+#[allow(dead_code)]
+impl<T> SparseGrid<T> {
+    pub(crate) fn new(default: T) -> Self{
+        Self{cells: HashMap::new(), default, bounds: None, orientation: Orientation::Screen}
+    }
+
+    pub(crate) fn orientation(&self) -> Orientation{
+        self.orientation
+    }
+
+    //same picture with y growing the other way, every cell is mirrored at y = 0
+    pub(crate) fn to_orientation(&self, orientation: Orientation) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        if orientation != self.orientation {
+            let mirror = |p: &Point2D| Point2D::new(*p.x(), -*p.y());
+            grid.cells = self.cells.iter().map(|(p, value)| (mirror(p), value.clone())).collect();
+            grid.bounds = self.bounds.and_then(|bounds| Rect::bounding([mirror(bounds.min_corner()), mirror(bounds.max_corner())].iter()));
+        }
+        grid.orientation = orientation;
+        grid
+    }
+
+    pub(crate) fn default_value(&self) -> &T{
+        &self.default
+    }
+
+    //number of cells written to
+    pub(crate) fn len(&self) -> usize{
+        self.cells.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool{
+        self.cells.is_empty()
+    }
+
+    pub(crate) fn bounds(&self) -> Option<Rect>{
+        self.bounds
+    }
+
+    fn grow(&mut self, point: &Point2D){
+        let point_box = Rect::from_point(*point);
+        self.bounds = Some(self.bounds.map_or(point_box, |bounds| bounds.hull(&point_box)));
+    }
+
+    pub(crate) fn contains(&self, point: &Point2D) -> bool{
+        self.cells.contains_key(point)
+    }
+
+    pub(crate) fn get(&self, point: &Point2D) -> &T{
+        self.cells.get(point).unwrap_or(&self.default)
+    }
+
+    //the cell at point, written with the default value first if it was not set yet
+    pub(crate) fn get_mut(&mut self, point: &Point2D) -> &mut T
+    where
+        T: Clone,
+    {
+        self.grow(point);
+        self.cells.entry(*point).or_insert_with(|| self.default.clone())
+    }
+
+    pub(crate) fn insert(&mut self, point: &Point2D, value: T) -> Option<T>{
+        self.grow(point);
+        self.cells.insert(*point, value)
+    }
+
+    pub(crate) fn remove(&mut self, point: &Point2D) -> Option<T>{
+        self.cells.remove(point)
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Point2D, &T)>{
+        self.cells.iter()
+    }
+
+    pub(crate) fn points(&self) -> impl Iterator<Item = &Point2D>{
+        self.cells.keys()
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &T>{
+        self.cells.values()
+    }
+
+    //dense copy of the bounding rectangle, its min corner moved to the origin
+    pub(crate) fn to_dense(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let Some(bounds) = self.bounds else {
+            return Grid::default();
+        };
+        let origin = *bounds.min_corner();
+        Grid::from_fn(bounds.extent(0), bounds.extent(1), |x, y| {
+            self.get(&(origin + Point2D::new(x, y))).clone()
+        })
+    }
+}
+
+impl<T: Clone> GridLike<T> for SparseGrid<T> {
+    fn get_element(&self, point: &Point2D) -> Option<&T>{
+        Some(self.get(point))
+    }
+
+    fn set(&mut self, point: &Point2D, value: T) -> T{
+        self.insert(point, value).unwrap_or_else(|| self.default.clone())
+    }
+
+    fn bounding_rect(&self) -> Option<Rect>{
+        self.bounds
+    }
+
+    //the bounds only tell where cells were written, there is no edge
+    fn topology(&self) -> Topology{
+        Topology::Infinite
+    }
+
+    fn orientation(&self) -> Orientation{
+        self.orientation
+    }
+
+    fn step(&self, point: &Point2D, direction: &Direction) -> Option<Point2D>{
+        Some(*point + direction.to_point_in(self.orientation))
+    }
+}
@@ -0,0 +1,212 @@
+use std::ops::Index;
+use crate::geometry::grid::Grid;
+use crate::geometry::point::Point2D;
+use crate::geometry::rect::Rect;
+
+//the eight symmetries of a square (dihedral group D4), rotations turn the picture as seen on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum Symmetry {
+    Identity,
+    RotateRight,
+    Rotate180,
+    RotateLeft,
+    //mirrored left to right
+    FlipHorizontal,
+    //mirrored top to bottom
+    FlipVertical,
+    //mirrored along the main diagonal
+    Transpose,
+    //mirrored along the other diagonal
+    AntiTranspose
+}
+
+#[allow(dead_code)]
+impl Symmetry {
+    pub(crate) fn all() -> [Symmetry; 8]{
+        [Symmetry::Identity, Symmetry::RotateRight, Symmetry::Rotate180, Symmetry::RotateLeft,
+         Symmetry::FlipHorizontal, Symmetry::FlipVertical, Symmetry::Transpose, Symmetry::AntiTranspose]
+    }
+
+    pub(crate) fn rotations() -> [Symmetry; 4]{
+        [Symmetry::Identity, Symmetry::RotateRight, Symmetry::Rotate180, Symmetry::RotateLeft]
+    }
+
+    pub(crate) fn swaps_axes(self) -> bool{
+        matches!(self, Symmetry::RotateRight | Symmetry::RotateLeft | Symmetry::Transpose | Symmetry::AntiTranspose)
+    }
+
+    pub(crate) fn inverse(self) -> Self{
+        match self {
+            Symmetry::RotateRight => Symmetry::RotateLeft,
+            Symmetry::RotateLeft => Symmetry::RotateRight,
+            other => other
+        }
+    }
+
+    //shape of the image of a width x height grid
+    pub(crate) fn shape(self, width: usize, height: usize) -> (usize, usize){
+        if self.swaps_axes() { (height, width) } else { (width, height) }
+    }
+
+    //cell of the original width x height grid that ends up at (x, y) of the image
+    pub(crate) fn source(self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize){
+        match self {
+            Symmetry::Identity => (x, y),
+            Symmetry::RotateRight => (y, height - 1 - x),
+            Symmetry::Rotate180 => (width - 1 - x, height - 1 - y),
+            Symmetry::RotateLeft => (width - 1 - y, x),
+            Symmetry::FlipHorizontal => (width - 1 - x, y),
+            Symmetry::FlipVertical => (x, height - 1 - y),
+            Symmetry::Transpose => (y, x),
+            Symmetry::AntiTranspose => (width - 1 - y, height - 1 - x)
+        }
+    }
+}
+
+//a grid seen through one of its symmetries without copying any cell
+#[derive(Debug)]
+pub(crate) struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    symmetry: Symmetry
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+#[allow(dead_code)]
+impl<'a, T> GridView<'a, T> {
+    pub(crate) fn symmetry(&self) -> Symmetry{
+        self.symmetry
+    }
+
+    pub(crate) fn shape(&self) -> (usize, usize){
+        self.symmetry.shape(self.grid.width(), self.grid.height())
+    }
+
+    pub(crate) fn width(&self) -> usize{
+        self.shape().0
+    }
+
+    pub(crate) fn height(&self) -> usize{
+        self.shape().1
+    }
+
+    pub(crate) fn contains(&self, point: &Point2D) -> bool{
+        let (width, height) = self.shape();
+        !point.is_out_of_bounds(width, height)
+    }
+
+    //point of the underlying grid shown at point of the view
+    pub(crate) fn source(&self, point: &Point2D) -> Option<Point2D>{
+        if !self.contains(point) {
+            return None;
+        }
+        let (x, y) = self.symmetry.source(*point.x() as usize, *point.y() as usize, self.grid.width(), self.grid.height());
+        Some(Point2D::new(x, y))
+    }
+
+    pub(crate) fn get_element(&self, point: &Point2D) -> Option<&'a T>{
+        self.source(point).and_then(|p| self.grid.get_element(&p))
+    }
+
+    //every point of the view row by row
+    pub(crate) fn points(&self) -> impl Iterator<Item = Point2D>{
+        let (width, height) = self.shape();
+        (0..width * height).map(move |offset| Point2D::new(offset % width, offset / width))
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Point2D, &'a T)>{
+        let view = *self;
+        self.points().map(move |p| (p, view.get_element(&p).unwrap()))
+    }
+
+    pub(crate) fn to_grid(self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let (width, height) = self.shape();
+        let (w, h) = (self.grid.width(), self.grid.height());
+        self.grid.remap(width, height, |x, y| self.symmetry.source(x, y, w, h))
+    }
+}
+
+impl<T> Index<&Point2D> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, point: &Point2D) -> &T {
+        self.get_element(point).unwrap_or_else(|| panic!("{} is outside of the view", point))
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Grid<T> {
+    pub(crate) fn view(&self, symmetry: Symmetry) -> GridView<'_, T>{
+        GridView{grid: self, symmetry}
+    }
+
+    pub(crate) fn views(&self) -> impl Iterator<Item = GridView<'_, T>>{
+        Symmetry::all().into_iter().map(|symmetry| self.view(symmetry))
+    }
+
+    pub(crate) fn transformed(&self, symmetry: Symmetry) -> Self
+    where
+        T: Clone,
+    {
+        self.view(symmetry).to_grid()
+    }
+
+    //the smallest of the eight images, so grids related by a symmetry share the same canonical form
+    pub(crate) fn canonical(&self) -> Self
+    where
+        T: Ord + Clone,
+    {
+        self.views()
+            .min_by(|a, b| a.shape().cmp(&b.shape()).then_with(|| {
+                a.points().map(|p| &a[&p]).cmp(b.points().map(|p| &b[&p]))
+            }))
+            .unwrap()
+            .to_grid()
+    }
+
+    //copy of the cells inside rect, which has to lie within the grid
+    pub(crate) fn sub_grid(&self, rect: &Rect) -> Self
+    where
+        T: Clone,
+    {
+        assert!(self.contains(rect.min_corner()) && self.contains(rect.max_corner()), "Sub-grid outside of the grid");
+        let (x0, y0) = (*rect.min_corner().x() as usize, *rect.min_corner().y() as usize);
+        self.remap(rect.extent(0), rect.extent(1), |x, y| (x0 + x, y0 + y))
+    }
+
+    //the grid cut into k x k squares, arranged as they were; both sides must be multiples of k
+    pub(crate) fn tiles(&self, k: usize) -> Grid<Self>
+    where
+        T: Clone,
+    {
+        assert!(k > 0 && self.width().is_multiple_of(k) && self.height().is_multiple_of(k), "Grid cannot be split into {} x {} tiles", k, k);
+        Grid::from_fn(self.width() / k, self.height() / k, |i, j| {
+            self.sub_grid(&Rect::new(Point2D::new(i * k, j * k), Point2D::new(i * k + k - 1, j * k + k - 1)))
+        })
+    }
+
+    //inverse of tiles, all tiles must have the same shape
+    pub(crate) fn from_tiles(tiles: &Grid<Self>) -> Self
+    where
+        T: Clone,
+    {
+        let Some(first) = tiles.values().next() else {
+            return Self::default();
+        };
+        let (tile_width, tile_height) = (first.width(), first.height());
+        assert!(tiles.values().all(|tile| tile.width() == tile_width && tile.height() == tile_height), "Tiles of different shapes");
+        Self::from_fn(tiles.width() * tile_width, tiles.height() * tile_height, |x, y| {
+            let tile = &tiles[&Point2D::new(x / tile_width, y / tile_height)];
+            tile[&Point2D::new(x % tile_width, y % tile_height)].clone()
+        })
+    }
+}
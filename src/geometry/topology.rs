@@ -0,0 +1,41 @@
+use crate::geometry::point::Point2D;
+use crate::geometry::rect::Rect;
+
+//what lies beyond the edges of a grid
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub(crate) enum Topology {
+    //nothing, points outside of the grid do not exist
+    #[default]
+    Bounded,
+    //leaving on one side comes back in on the opposite one
+    Toroidal,
+    //points outside of the grid exist but hold no cell
+    Infinite
+}
+
+#[allow(dead_code)]
+impl Topology {
+    //the point a position stands for in a grid covering bounds, None if there is no such point
+    pub(crate) fn resolve(self, point: &Point2D, bounds: &Rect) -> Option<Point2D>{
+        match self {
+            Topology::Bounded => bounds.contains(point).then_some(*point),
+            Topology::Toroidal => {
+                let min = *bounds.min_corner();
+                let local = *point - min;
+                let x = local.x().rem_euclid(bounds.extent(0) as isize);
+                let y = local.y().rem_euclid(bounds.extent(1) as isize);
+                Some(min + Point2D::new(x, y))
+            }
+            Topology::Infinite => Some(*point)
+        }
+    }
+
+    //shorthand for wrapping a point into the width x height rectangle at the origin, None if it is empty
+    pub(crate) fn wrap(point: &Point2D, width: usize, height: usize) -> Option<Point2D>{
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Topology::Toroidal.resolve(point, &Rect::from_size(width, height))
+    }
+}
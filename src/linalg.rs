@@ -0,0 +1,352 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use crate::math::{ceil_div, extended_gcd, floor_div, gcd};
+
+//exact fraction kept in lowest terms with a positive denominator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Rational {
+    num: i128,
+    den: i128
+}
+
+#[allow(dead_code)]
+impl Rational {
+    pub(crate) fn new(num: i128, den: i128) -> Self{
+        assert_ne!(den, 0, "Zero denominator");
+        let g = gcd(num, den) * den.signum();
+        Self{num: num / g, den: den / g}
+    }
+
+    pub(crate) fn integer(value: i128) -> Self{
+        Self{num: value, den: 1}
+    }
+
+    pub(crate) fn zero() -> Self{
+        Self::integer(0)
+    }
+
+    pub(crate) fn one() -> Self{
+        Self::integer(1)
+    }
+
+    pub(crate) fn numerator(&self) -> i128{
+        self.num
+    }
+
+    pub(crate) fn denominator(&self) -> i128{
+        self.den
+    }
+
+    pub(crate) fn is_zero(&self) -> bool{
+        self.num == 0
+    }
+
+    pub(crate) fn is_integer(&self) -> bool{
+        self.den == 1
+    }
+
+    pub(crate) fn to_integer(self) -> Option<i128>{
+        if self.is_integer() { Some(self.num) } else { None }
+    }
+
+    pub(crate) fn floor(&self) -> i128{
+        floor_div(self.num, self.den)
+    }
+
+    pub(crate) fn ceil(&self) -> i128{
+        ceil_div(self.num, self.den)
+    }
+
+    pub(crate) fn abs(&self) -> Self{
+        Self{num: self.num.abs(), den: self.den}
+    }
+
+    pub(crate) fn recip(&self) -> Self{
+        Self::new(self.den, self.num)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self::integer(value)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        let g = gcd(self.den, other.den);
+        Rational::new(self.num * (other.den / g) + other.num * (self.den / g), self.den / g * other.den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational{num: -self.num, den: self.den}
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        //cross-reduce first to keep the intermediate products small
+        let g1 = gcd(self.num, other.den).max(1);
+        let g2 = gcd(other.num, self.den).max(1);
+        Rational::new((self.num / g1) * (other.num / g2), (self.den / g2) * (other.den / g1))
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Rational) -> Rational {
+        self * other.recip()
+    }
+}
+
+//dense matrix of exact fractions stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Matrix {
+    n_rows: usize,
+    n_cols: usize,
+    data: Vec<Rational>
+}
+
+#[allow(dead_code)]
+impl Matrix {
+    pub(crate) fn zeros(n_rows: usize, n_cols: usize) -> Self{
+        Self{n_rows, n_cols, data: vec![Rational::zero(); n_rows * n_cols]}
+    }
+
+    pub(crate) fn identity(n: usize) -> Self{
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m.set(i, i, Rational::one());
+        }
+        m
+    }
+
+    pub(crate) fn from_rows<T: Copy + Into<i128>>(rows: &[Vec<T>]) -> Self{
+        let n_cols = rows.first().map_or(0, |r| r.len());
+        assert!(rows.iter().all(|r| r.len() == n_cols), "Rows of different length");
+        Self{
+            n_rows: rows.len(),
+            n_cols,
+            data: rows.iter().flatten().map(|&v| Rational::integer(v.into())).collect()
+        }
+    }
+
+    //matrix whose columns are the given vectors
+    pub(crate) fn from_columns<T: Copy + Into<i128>>(columns: &[Vec<T>]) -> Self{
+        Self::from_rows(columns).transpose()
+    }
+
+    pub(crate) fn shape(&self) -> (usize, usize){
+        (self.n_rows, self.n_cols)
+    }
+
+    pub(crate) fn get(&self, row: usize, col: usize) -> Rational{
+        self.data[row * self.n_cols + col]
+    }
+
+    pub(crate) fn set(&mut self, row: usize, col: usize, value: Rational){
+        self.data[row * self.n_cols + col] = value;
+    }
+
+    pub(crate) fn transpose(&self) -> Self{
+        let mut t = Self::zeros(self.n_cols, self.n_rows);
+        for r in 0..self.n_rows {
+            for c in 0..self.n_cols {
+                t.set(c, r, self.get(r, c));
+            }
+        }
+        t
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize){
+        for c in 0..self.n_cols {
+            self.data.swap(a * self.n_cols + c, b * self.n_cols + c);
+        }
+    }
+
+    //reduced row echelon form in place, returns the pivot columns and
+    //the factor by which the determinant of the leading square block changed
+    fn row_reduce(&mut self) -> (Vec<usize>, Rational){
+        let mut pivots = Vec::new();
+        let mut factor = Rational::one();
+        let mut row = 0;
+        for col in 0..self.n_cols {
+            if row == self.n_rows {
+                break;
+            }
+            let Some(pivot) = (row..self.n_rows).find(|&r| !self.get(r, col).is_zero()) else {
+                continue;
+            };
+            if pivot != row {
+                self.swap_rows(pivot, row);
+                factor = -factor;
+            }
+            let p = self.get(row, col);
+            factor = factor / p;
+            for c in col..self.n_cols {
+                self.set(row, c, self.get(row, c) / p);
+            }
+            for r in 0..self.n_rows {
+                let f = self.get(r, col);
+                if r != row && !f.is_zero() {
+                    for c in col..self.n_cols {
+                        self.set(r, c, self.get(r, c) - f * self.get(row, c));
+                    }
+                }
+            }
+            pivots.push(col);
+            row += 1;
+        }
+        (pivots, factor)
+    }
+
+    pub(crate) fn rank(&self) -> usize{
+        self.clone().row_reduce().0.len()
+    }
+
+    pub(crate) fn determinant(&self) -> Rational{
+        assert_eq!(self.n_rows, self.n_cols, "Determinant of a non-square matrix");
+        let mut m = self.clone();
+        let (pivots, factor) = m.row_reduce();
+        if pivots.len() < self.n_rows {
+            Rational::zero()
+        } else {
+            factor.recip()
+        }
+    }
+
+    //the unique x with self * x = rhs, None when the matrix is singular
+    pub(crate) fn solve(&self, rhs: &[Rational]) -> Option<Vec<Rational>>{
+        assert_eq!(self.n_rows, self.n_cols, "Solve needs a square matrix");
+        assert_eq!(rhs.len(), self.n_rows, "Right-hand side of wrong length");
+        let n = self.n_rows;
+        let mut augmented = Self::zeros(n, n + 1);
+        for (r, &value) in rhs.iter().enumerate() {
+            for c in 0..n {
+                augmented.set(r, c, self.get(r, c));
+            }
+            augmented.set(r, n, value);
+        }
+        let (pivots, _) = augmented.row_reduce();
+        if pivots.len() < n || pivots[n - 1] != n - 1 {
+            return None;
+        }
+        Some((0..n).map(|r| augmented.get(r, n)).collect())
+    }
+
+    //the unique solution if it exists and is integral
+    pub(crate) fn solve_integer(&self, rhs: &[i128]) -> Option<Vec<i128>>{
+        let rhs: Vec<Rational> = rhs.iter().map(|&v| Rational::integer(v)).collect();
+        self.solve(&rhs)?.into_iter().map(|v| v.to_integer()).collect()
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in 0..self.n_rows {
+            let row: Vec<String> = (0..self.n_cols).map(|c| self.get(r, c).to_string()).collect();
+            writeln!(f, "[{}]", row.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+//cheapest non-negative integer (n_a, n_b) with n_a * a + n_b * b = target and both counts
+//at most limit, for non-negative costs; collinear a and b are handled through the
+//one-dimensional Diophantine equation along an axis where they do not both vanish
+pub(crate) fn min_cost_combination(a: [i128; 2], b: [i128; 2], target: [i128; 2],
+                                   costs: [i128; 2], limit: Option<i128>) -> Option<[i128; 2]>{
+    let upper = limit.unwrap_or(i128::MAX);
+    let in_range = |n: i128| (0..=upper).contains(&n);
+    let system = Matrix::from_columns(&[a.to_vec(), b.to_vec()]);
+    if !system.determinant().is_zero() {
+        let n = system.solve_integer(&target)?;
+        return if n.iter().all(|&v| in_range(v)) { Some([n[0], n[1]]) } else { None };
+    }
+    //everything has to lie on one line through the origin
+    if Matrix::from_columns(&[a.to_vec(), b.to_vec(), target.to_vec()]).rank() > system.rank() {
+        return None;
+    }
+    let Some(axis) = (0..2).find(|&k| a[k] != 0 || b[k] != 0) else {
+        return if target == [0, 0] { Some([0, 0]) } else { None };
+    };
+    let (a, b, t) = (a[axis], b[axis], target[axis]);
+    if a == 0 || b == 0 {
+        //the other button does nothing, so it is never pressed
+        let (step, index) = if a == 0 { (b, 1) } else { (a, 0) };
+        if t % step != 0 || !in_range(t / step) {
+            return None;
+        }
+        let mut n = [0, 0];
+        n[index] = t / step;
+        return Some(n);
+    }
+    let (g, u, v) = extended_gcd(a, b);
+    if t % g != 0 {
+        return None;
+    }
+    //all solutions are n_a = x0 + s * db, n_b = y0 - s * da
+    let (x0, y0, da, db) = (u * (t / g), v * (t / g), a / g, b / g);
+    let (mut lo, mut hi) = (i128::MIN, i128::MAX);
+    for (base, step) in [(x0, db), (y0, -da)] {
+        //0 <= base + s * step <= upper
+        let (from, to) = if step > 0 {
+            (ceil_div(-base, step), if upper == i128::MAX { i128::MAX } else { floor_div(upper - base, step) })
+        } else {
+            (if upper == i128::MAX { i128::MIN } else { ceil_div(upper - base, step) }, floor_div(-base, step))
+        };
+        lo = lo.max(from);
+        hi = hi.min(to);
+    }
+    if lo > hi {
+        return None;
+    }
+    //the cost changes linearly with s, so the optimum sits at an end of the range
+    let slope = costs[0] * db - costs[1] * da;
+    let s = if slope > 0 || (slope == 0 && lo != i128::MIN) { lo } else { hi };
+    if s == i128::MIN || s == i128::MAX {
+        return None;
+    }
+    Some([x0 + s * db, y0 - s * da])
+}
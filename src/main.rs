@@ -1,9 +1,21 @@
-use crate::utils::Solve;
+use crate::utils::{file_fingerprint, set_check_expected, Solve};
+use crate::records::{short_fingerprint, AnswerStore};
+use crate::alloc::AllocStats;
+use crate::geometry::{set_recording, FrameFormat, RecordOptions};
+use std::collections::HashMap;
 use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
 use chrono::{Datelike, Local};
 
 mod utils;
+mod alloc;
 mod geometry;
+mod linalg;
+mod math;
+mod records;
+mod server;
+mod repl;
 mod y2024;
 mod y2015;
 mod y2016;
@@ -35,53 +47,223 @@ fn format_duration(duration: Duration) -> String{
     }
 }
 
-fn run(a: &mut Box<dyn Solve>, n_iterations: u32, test_mode: bool, bruteforce: bool) {
+fn format_bytes(bytes: usize) -> String{
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[0])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+fn format_alloc_stats(stats: &AllocStats) -> String{
+    format!("peak {}, {} allocations", format_bytes(stats.peak_bytes), stats.allocations)
+}
+
+//number, name and entry point of a puzzle part
+type PartMethod = (u8, &'static str, Box<dyn Fn(&mut Box<dyn Solve>, bool) -> Result<String, String>>);
+
+fn run(a: &mut Box<dyn Solve>, store: &mut AnswerStore, n_iterations: u32, test_mode: bool, bruteforce: bool) {
     println!("{}", "-".repeat(50));
     println!(":::Day {}:::", a.get_label().day);
     if bruteforce {
         a.apply_bruteforce();
     }
 
-    if let Err(_) = a.read_input(test_mode) {
+    let (parsed, parse_stats) = alloc::measure(|| a.read_input(test_mode));
+    if parsed.is_err() {
         println!("Cannot read puzzle input");
         return;
     }
+    if let Some(stats) = &parse_stats {
+        println!("Memory parse: {}", format_alloc_stats(stats));
+    }
+
+    let source = a.get_label().get_puzzle_input_path(test_mode);
+    let day = a.get_label().day;
+    if let Ok(fingerprint) = file_fingerprint(&source) {
+        println!("Input fingerprint: {}", short_fingerprint(&fingerprint));
+        let mut is_reference = true;
+        if !test_mode {
+            if let Err(msg) = store.check_reference(day, &fingerprint) {
+                println!("{}", msg);
+                is_reference = false;
+            };
+        }
+        set_check_expected(is_reference);
+        if let Some(stats) = parse_stats {
+            store.record_memory(day, &fingerprint, &source, 0, stats);
+        }
+        a.get_label_mut().fingerprint = Some(fingerprint);
+    }
 
     if let Err(msg) = a.info() {println!("{}", msg)};
 
     // Define the method closures with explicit types
-    let methods: [(&str, Box<dyn Fn(&mut Box<dyn Solve>, bool) -> Result<String, String>>); 2] = [
+    let methods: [PartMethod; 2] = [
         (
+            1,
             "Part 1",
             Box::new(|a: &mut Box<dyn Solve>, test_mode| a.compute_part1_answer(test_mode))
         ),
         (
+            2,
             "Part 2",
             Box::new(|a: &mut Box<dyn Solve>, test_mode| a.compute_part2_answer(test_mode))
         ),
     ];
+    let fingerprint = a.get_label().fingerprint.clone();
 
     // Iterate over the methods
-    for (part_name, method) in methods.iter() {
-        if n_iterations > 0 {
-            let d = timeit(|| { method(a, test_mode) }, n_iterations);
+    for (part, part_name, method) in methods.iter() {
+        let stats = if n_iterations > 0 {
+            let (d, stats) = alloc::measure(|| timeit(|| { method(a, test_mode) }, n_iterations));
             if let Ok(d) = d {
                 println!("Time taken {}: {}", part_name, format_duration(d));
+                if let Some(fingerprint) = &fingerprint {
+                    store.record_benchmark(day, fingerprint, &source, *part, d, n_iterations);
+                }
             }
+            stats.map(|s| s.per_iteration(n_iterations))
         }else{
-            match method(a, test_mode) {
+            let (result, stats) = alloc::measure(|| method(a, test_mode));
+            match result {
                 Ok(result) => {
                     println!("{}", result);
+                    if let Some(fingerprint) = &fingerprint {
+                        if let Err(msg) = store.verify_answer(day, fingerprint, &source, *part, &result) {
+                            println!("{}", msg);
+                        }
+                    }
                 }
                 Err(msg) => {
                     {println!("Error: {}", msg);}
                 }
             }
+            stats
+        };
+        if let Some(stats) = stats {
+            println!("Memory {}: {}", part_name, format_alloc_stats(&stats));
+            if let Some(fingerprint) = &fingerprint {
+                store.record_memory(day, fingerprint, &source, *part, stats);
+            }
         }
     }
     println!("{}", "\n");
 }
 
+const YEARS: [u16; 4] = [2015, 2016, 2017, 2024];
+
+fn collect_solutions(year: u16) -> HashMap<u8, Box<dyn Solve>>{
+    match year{
+        2015 => y2015::collect_solutions(),
+        2016 => y2016::collect_solutions(),
+        2017 => y2017::collect_solutions(),
+        2024 => y2024::collect_solutions(),
+        _ => unreachable!()
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String{
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+fn solve_input(year: u16, day: u8, path: &str, fingerprint: &str, store: &mut AnswerStore) -> [String; 2] {
+    let mut solutions = collect_solutions(year);
+    let Some(mut a) = solutions.remove(&day) else {
+        return [String::from("no solution"), String::from("no solution")];
+    };
+    a.get_label_mut().input_override = Some(path.to_owned());
+    a.get_label_mut().fingerprint = Some(fingerprint.to_owned());
+    set_check_expected(store.is_reference(day, fingerprint));
+
+    match panic::catch_unwind(AssertUnwindSafe(|| a.read_input(false))) {
+        Ok(Ok(())) if a.get_label().has_input => {},
+        Ok(_) => return [String::from("cannot parse"), String::from("cannot parse")],
+        Err(payload) => {
+            let msg = format!("panic: {}", panic_message(payload));
+            return [msg.clone(), msg];
+        }
+    }
+
+    [1, 2].map(|part| {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| match part {
+            1 => a.compute_part1_answer(false),
+            _ => a.compute_part2_answer(false)
+        }));
+        match result {
+            Ok(Ok(answer)) => {
+                let is_new = store.stored_answer(day, fingerprint, part).is_none();
+                match store.verify_answer(day, fingerprint, path, part, &answer) {
+                    Ok(()) if is_new => String::from("new"),
+                    Ok(()) => String::from("pass"),
+                    Err(_) => String::from("FAIL")
+                }
+            }
+            Ok(Err(msg)) => msg,
+            Err(payload) => format!("panic: {}", panic_message(payload))
+        }
+    })
+}
+
+//solves every file of a directory with a single day and checks each one against its own stored answers
+fn run_inputs(year: u16, day: u8, dir: &str, store: &mut AnswerStore) {
+    let mut paths: Vec<String> = match fs::read_dir(dir) {
+        Ok(entries) => entries.flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+        Err(e) => {
+            println!("Cannot read input directory {}: {}", dir, e);
+            return;
+        }
+    };
+    paths.sort();
+
+    println!(":::Day {} against {} inputs:::", day, paths.len());
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut rows: Vec<[String; 4]> = Vec::new();
+    for path in paths.iter() {
+        let Ok(fingerprint) = file_fingerprint(path) else {
+            rows.push([path.clone(), String::from("-"), String::from("unreadable"), String::from("unreadable")]);
+            continue;
+        };
+        let [part1, part2] = solve_input(year, day, path, &fingerprint, store);
+        rows.push([path.clone(), short_fingerprint(&fingerprint).to_owned(), part1, part2]);
+    }
+    panic::set_hook(default_hook);
+    set_check_expected(true);
+
+    let header = [String::from("Input"), String::from("Fingerprint"), String::from("Part 1"), String::from("Part 2")];
+    let widths: Vec<usize> = (0..4)
+        .map(|c| rows.iter().chain([&header]).map(|row| row[c].chars().count()).max().unwrap_or(0))
+        .collect();
+    for row in [&header].into_iter().chain(rows.iter()) {
+        let cells: Vec<String> = row.iter().zip(widths.iter())
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join(" | "));
+    }
+    let n_passed = rows.iter()
+        .filter(|row| row[2..].iter().all(|status| status == "pass" || status == "new" || status.ends_with("not solved")))
+        .count();
+    println!("{}/{} inputs passed", n_passed, rows.len());
+}
+
 fn main() {
     let current_date = Local::now().date_naive();
     let mut year = current_date.year() as u16;
@@ -90,35 +272,84 @@ fn main() {
     let mut first_day: u8 = 1;
     let mut last_day: u8 = 25;
     let mut bruteforce: bool = false;
+    let mut inputs: Option<String> = None;
+    let mut host = String::from("127.0.0.1");
+    let mut port: u16 = 8080;
+    //frames of simulations are only written when a format is given
+    let mut record: Option<FrameFormat> = None;
+    let mut record_options = RecordOptions::new(FrameFormat::Png);
 
     let args: Vec<String> = env::args().collect();
-    let mut itr = args.iter().skip(1);
+    let mut itr = args.iter().skip(1).peekable();
+    //"run" is the default command and may be omitted
+    let command = match itr.peek() {
+        Some(arg) if !arg.starts_with('-') => itr.next().unwrap().clone(),
+        _ => String::from("run")
+    };
 
     while let (Some(key), Some(value)) = (itr.next(), itr.next()) {
         match key.as_str() {
-            "-t" => test_mode = value.parse::<bool>().unwrap_or(test_mode),
-            "-b" => bruteforce = value.parse::<bool>().unwrap_or(bruteforce),
-            "-i" => n_iterations = value.parse::<u32>().unwrap_or(n_iterations),
-            "-fd" => first_day = value.parse::<u8>().unwrap_or(first_day),
-            "-ld" => last_day = value.parse::<u8>().unwrap_or(last_day),
-            "-y" => year = value.parse::<u16>().unwrap_or(year),
+            "-t" | "--test" => test_mode = value.parse::<bool>().unwrap_or(test_mode),
+            "-b" | "--bruteforce" => bruteforce = value.parse::<bool>().unwrap_or(bruteforce),
+            "-i" | "--iterations" => n_iterations = value.parse::<u32>().unwrap_or(n_iterations),
+            "-fd" | "--first-day" => first_day = value.parse::<u8>().unwrap_or(first_day),
+            "-ld" | "--last-day" => last_day = value.parse::<u8>().unwrap_or(last_day),
+            "-d" | "--day" => {
+                first_day = value.parse::<u8>().unwrap_or(first_day);
+                last_day = first_day;
+            }
+            "-y" | "--year" => year = value.parse::<u16>().unwrap_or(year),
+            "--inputs" => inputs = Some(value.clone()),
+            "--host" => host = value.clone(),
+            "--port" => port = value.parse::<u16>().unwrap_or(port),
+            "-r" | "--record" => record = FrameFormat::from_name(value),
+            "--record-every" => record_options.every = value.parse::<usize>().unwrap_or(record_options.every),
+            "--record-limit" => record_options.limit = value.parse::<usize>().unwrap_or(record_options.limit),
+            "--record-scale" => record_options.scale = value.parse::<usize>().unwrap_or(record_options.scale),
             _ => {}
         }
     }
+
+    match command.as_str() {
+        "run" => {},
+        "repl" => {
+            repl::repl(year, first_day, test_mode, bruteforce);
+            return;
+        }
+        "serve" => {
+            if let Err(e) = server::serve(&format!("{}:{}", host, port)) {
+                println!("Cannot start server: {}", e);
+            }
+            return;
+        }
+        _ => {
+            println!("Unknown command {}", command);
+            return;
+        }
+    }
     last_day = [first_day, last_day].into_iter().max().unwrap();
+    set_recording(record.map(|format| RecordOptions{format, ..record_options}));
 
-    let mut solutions =
-    match year{
-        2015 => y2015::collect_solutions(),
-        2016 => y2016::collect_solutions(),
-        2017 => y2017::collect_solutions(),
-        2024 => y2024::collect_solutions(),
-        _ => unreachable!()
-    };
+    let mut solutions = collect_solutions(year);
 
-    for day in first_day..=last_day {
-        if let Some(a) = solutions.get_mut(&day) {
-            run(a, n_iterations, test_mode, bruteforce);
+    let mut store = AnswerStore::load(year).unwrap_or_else(|msg| {
+        println!("{}", msg);
+        AnswerStore::detached(year)
+    });
+
+    if let Some(dir) = inputs {
+        for day in first_day..=last_day {
+            run_inputs(year, day, &dir, &mut store);
+        }
+    } else {
+        for day in first_day..=last_day {
+            if let Some(a) = solutions.get_mut(&day) {
+                run(a, &mut store, n_iterations, test_mode, bruteforce);
+            }
         }
     }
+
+    if let Err(e) = store.save() {
+        println!("Cannot store answers: {}", e);
+    }
 }
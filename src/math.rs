@@ -0,0 +1,85 @@
+//number theory on i128 so that products of two moduli below 2^63 never overflow
+
+pub(crate) fn gcd(a: i128, b: i128) -> i128{
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+pub(crate) fn lcm(a: i128, b: i128) -> i128{
+    if a == 0 || b == 0 {
+        0
+    } else {
+        (a / gcd(a, b) * b).abs()
+    }
+}
+
+#[allow(dead_code)]
+pub(crate) fn lcm_all<I: IntoIterator<Item = i128>>(values: I) -> i128{
+    values.into_iter().fold(1, lcm)
+}
+
+//(g, x, y) with a*x + b*y = g = gcd(a, b)
+pub(crate) fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128){
+    if b == 0 {
+        (a.abs(), a.signum(), 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a.rem_euclid(b));
+        (g, y, x - a.div_euclid(b) * y)
+    }
+}
+
+pub(crate) fn floor_div(a: i128, b: i128) -> i128{
+    let q = a / b;
+    if (a % b != 0) && ((a < 0) != (b < 0)) { q - 1 } else { q }
+}
+
+pub(crate) fn ceil_div(a: i128, b: i128) -> i128{
+    -floor_div(-a, b)
+}
+
+//x in 0..modulus with a*x = 1 (mod modulus), None unless a and modulus are coprime
+pub(crate) fn mod_inverse(a: i128, modulus: i128) -> Option<i128>{
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    if g == 1 { Some(x.rem_euclid(modulus)) } else { None }
+}
+
+//base^exp mod modulus by repeated squaring
+pub(crate) fn mod_pow(base: i128, mut exp: u64, modulus: i128) -> i128{
+    let mut result = 1 % modulus;
+    let mut base = base.rem_euclid(modulus);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result
+}
+
+//x = residue (mod modulus) for every pair, moduli need not be coprime;
+//the answer is (x, lcm of the moduli) with x in 0..lcm, None if the congruences contradict each other
+pub(crate) fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)>{
+    congruences.iter().try_fold((0, 1), |(r1, m1), &(r2, m2)| {
+        let g = gcd(m1, m2);
+        let diff = r2 - r1;
+        if diff % g != 0 {
+            return None;
+        }
+        let m = m1 / g * m2;
+        //r1 + m1 * k = r2 (mod m2) with k = diff/g * inverse(m1/g) (mod m2/g)
+        let k = (diff / g).rem_euclid(m2 / g) * mod_inverse(m1 / g, m2 / g)? % (m2 / g);
+        Some(((r1 + m1 * k).rem_euclid(m), m))
+    })
+}
+
+//sum of the divisors of every n in 0..=limit, counting divisor d of n only while n/d <= max_multiple
+pub(crate) fn divisor_sums(limit: usize, max_multiple: Option<usize>) -> Vec<usize>{
+    let mut sums = vec![0; limit + 1];
+    for d in 1..=limit {
+        let last = max_multiple.map_or(limit, |k| (d * k).min(limit));
+        for n in (d..=last).step_by(d) {
+            sums[n] += d;
+        }
+    }
+    sums
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::alloc::AllocStats;
+use crate::utils::PUZZLE_INPUT;
+
+//answers and timings are only meaningful for the input they were produced on,
+//so everything is stored under the fingerprint of that input
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct PartRecord {
+    pub(crate) answer: Option<String>,
+    pub(crate) time_ns: Option<u64>,
+    pub(crate) iterations: Option<u32>,
+    pub(crate) peak_bytes: Option<u64>,
+    pub(crate) allocations: Option<u64>
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct InputRecord {
+    pub(crate) source: String,
+    pub(crate) parse: PartRecord,
+    pub(crate) parts: BTreeMap<u8, PartRecord>
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub(crate) struct DayRecord {
+    pub(crate) inputs: BTreeMap<String, InputRecord>
+}
+
+#[derive(Debug)]
+pub(crate) struct AnswerStore {
+    year: u16,
+    //fingerprint of the input the answers hard-coded in each solver belong to, tracked with the inputs
+    references: BTreeMap<u8, String>,
+    days: BTreeMap<u8, DayRecord>,
+    persist: bool,
+    modified: bool
+}
+
+pub(crate) fn short_fingerprint(fingerprint: &str) -> &str{
+    &fingerprint[..fingerprint.len().min(12)]
+}
+
+impl AnswerStore {
+    //answers of other inputs, timings and memory use: run output, kept next to the other generated files
+    pub(crate) fn directory(year: u16) -> String{
+        format!("output/{}/", year)
+    }
+
+    pub(crate) fn path(year: u16) -> String{
+        Self::directory(year) + "answers.json"
+    }
+
+    pub(crate) fn references_path(year: u16) -> String{
+        format!("{}{}/references.json", PUZZLE_INPUT, year)
+    }
+
+    //missing files start out empty
+    fn read_json<T: Default + DeserializeOwned>(path: &str) -> Result<T, String>{
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Cannot parse {}: {}", path, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+            Err(e) => Err(format!("Cannot read {}: {}", path, e))
+        }
+    }
+
+    pub(crate) fn load(year: u16) -> Result<Self, String>{
+        Ok(Self{
+            year,
+            references: Self::read_json(&Self::references_path(year))?,
+            days: Self::read_json(&Self::path(year))?,
+            persist: true,
+            modified: false
+        })
+    }
+
+    //in-memory store with only the tracked references, never written back; used when the stored file
+    //cannot be trusted or only the reference inputs are needed
+    pub(crate) fn detached(year: u16) -> Self{
+        Self{
+            year,
+            references: Self::read_json(&Self::references_path(year)).unwrap_or_default(),
+            days: BTreeMap::new(),
+            persist: false,
+            modified: false
+        }
+    }
+
+    pub(crate) fn is_reference(&self, day: u8, fingerprint: &str) -> bool{
+        self.references.get(&day).is_some_and(|reference| reference == fingerprint)
+    }
+
+    pub(crate) fn stored_answer(&self, day: u8, fingerprint: &str, part: u8) -> Option<&String>{
+        self.days.get(&day)?
+            .inputs.get(fingerprint)?
+            .parts.get(&part)?
+            .answer.as_ref()
+    }
+
+    fn input_mut(&mut self, day: u8, fingerprint: &str, source: &str) -> &mut InputRecord{
+        self.modified = true;
+        let record = self.days.entry(day).or_default()
+            .inputs.entry(fingerprint.to_owned()).or_default();
+        record.source = source.to_owned();
+        record
+    }
+
+    //the expected answers are only checked on the input listed in the tracked references
+    pub(crate) fn check_reference(&self, day: u8, fingerprint: &str) -> Result<(), String>{
+        match self.references.get(&day) {
+            None => Err(format!(
+                "Warning: no reference input for {} day {} in {}, expected answers are not checked",
+                self.year, day, Self::references_path(self.year)
+            )),
+            Some(reference) if reference == fingerprint => Ok(()),
+            Some(reference) => Err(format!(
+                "Warning: expected answers for {} day {} belong to input {}, loaded input is {}",
+                self.year, day, short_fingerprint(reference), short_fingerprint(fingerprint)
+            ))
+        }
+    }
+
+    //stores the answer if none is known for this input yet, otherwise compares against it
+    pub(crate) fn verify_answer(&mut self, day: u8, fingerprint: &str, source: &str, part: u8, answer: &str) -> Result<(), String>{
+        match self.stored_answer(day, fingerprint, part) {
+            Some(stored) if stored == answer => Ok(()),
+            Some(stored) => Err(format!(
+                "Warning: part {} answer '{}' differs from '{}' stored for input {}",
+                part, answer, stored, short_fingerprint(fingerprint)
+            )),
+            None => {
+                let record = self.input_mut(day, fingerprint, source);
+                record.parts.entry(part).or_default().answer = Some(answer.to_owned());
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn record_benchmark(&mut self, day: u8, fingerprint: &str, source: &str, part: u8, duration: Duration, iterations: u32){
+        let record = self.input_mut(day, fingerprint, source);
+        let part = record.parts.entry(part).or_default();
+        part.time_ns = Some(duration.as_nanos() as u64);
+        part.iterations = Some(iterations);
+    }
+
+    //part 0 stands for parsing the input
+    pub(crate) fn record_memory(&mut self, day: u8, fingerprint: &str, source: &str, part: u8, stats: AllocStats){
+        let record = self.input_mut(day, fingerprint, source);
+        let part = match part {
+            0 => &mut record.parse,
+            _ => record.parts.entry(part).or_default()
+        };
+        part.peak_bytes = Some(stats.peak_bytes as u64);
+        part.allocations = Some(stats.allocations as u64);
+    }
+
+    pub(crate) fn save(&mut self) -> io::Result<()>{
+        if !self.persist || !self.modified {
+            return Ok(());
+        }
+        let content = serde_json::to_string_pretty(&self.days)
+            .map_err(io::Error::other)?;
+        fs::create_dir_all(Self::directory(self.year))?;
+        fs::write(Self::path(self.year), content + "\n")?;
+        self.modified = false;
+        Ok(())
+    }
+}
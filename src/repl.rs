@@ -0,0 +1,186 @@
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+use crate::records::{short_fingerprint, AnswerStore};
+use crate::utils::{file_fingerprint, set_check_expected, Solve};
+use crate::{collect_solutions, format_duration, panic_message, timeit, YEARS};
+
+const HELP: &str = "\
+load <year> <day>   load a solution and its input
+example | real      switch to the example or the real input and reload
+reload              read the input again into a fresh solution
+bruteforce          toggle the bruteforce strategy and reload
+info                dump info()
+p1 | p2 | run       run part 1, part 2 or both
+time <part> [n]     average time of a part over n iterations
+canvas              render the parsed canvas, if the solution has one
+status              show what is loaded
+help                show this message
+quit                leave the repl";
+
+struct Session {
+    year: u16,
+    day: u8,
+    test_mode: bool,
+    bruteforce: bool,
+    solution: Option<Box<dyn Solve>>
+}
+
+impl Session {
+    fn load(&mut self) -> Result<(), String>{
+        self.solution = None;
+        if !YEARS.contains(&self.year) {
+            return Err(format!("No solutions for {}", self.year));
+        }
+        let mut a = collect_solutions(self.year).remove(&self.day)
+            .ok_or_else(|| format!("No solution for {} day {}", self.year, self.day))?;
+        if self.bruteforce {
+            a.apply_bruteforce();
+        }
+        let parsed = panic::catch_unwind(AssertUnwindSafe(|| a.read_input(self.test_mode)))
+            .map_err(|payload| format!("Parsing panicked: {}", panic_message(payload)))?;
+        if parsed.is_err() || !a.get_label().has_input {
+            return Err(String::from("Cannot read puzzle input"));
+        }
+
+        let source = a.get_label().get_puzzle_input_path(self.test_mode);
+        if let Ok(fingerprint) = file_fingerprint(&source) {
+            let is_reference = AnswerStore::detached(self.year).is_reference(self.day, &fingerprint);
+            if !self.test_mode && !is_reference {
+                println!("Input {} is not the reference input, expected answers are not checked", short_fingerprint(&fingerprint));
+            }
+            set_check_expected(self.test_mode || is_reference);
+            a.get_label_mut().fingerprint = Some(fingerprint);
+        }
+        println!("Loaded {} day {} from {}", self.year, self.day, source);
+        self.solution = Some(a);
+        Ok(())
+    }
+
+    fn solution(&mut self) -> Result<&mut Box<dyn Solve>, String>{
+        self.solution.as_mut().ok_or_else(|| String::from("Nothing loaded, use: load <year> <day>"))
+    }
+
+    fn run_part(&mut self, part: u8) -> Result<(), String>{
+        let test_mode = self.test_mode;
+        let a = self.solution()?;
+        let start = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| match part {
+            1 => a.compute_part1_answer(test_mode),
+            _ => a.compute_part2_answer(test_mode)
+        })).map_err(|payload| format!("Part {} panicked: {}", part, panic_message(payload)))?;
+        let elapsed = start.elapsed();
+        match result {
+            Ok(answer) => println!("{} ({})", answer, format_duration(elapsed)),
+            Err(msg) => println!("Error: {}", msg)
+        }
+        Ok(())
+    }
+
+    fn time_part(&mut self, part: u8, n_iterations: u32) -> Result<(), String>{
+        let test_mode = self.test_mode;
+        let a = self.solution()?;
+        let d = panic::catch_unwind(AssertUnwindSafe(|| timeit(|| match part {
+            1 => a.compute_part1_answer(test_mode),
+            _ => a.compute_part2_answer(test_mode)
+        }, n_iterations))).map_err(|payload| format!("Part {} panicked: {}", part, panic_message(payload)))??;
+        println!("Time taken Part {}: {} (over {} iterations)", part, format_duration(d), n_iterations);
+        Ok(())
+    }
+
+    fn render_canvas(&mut self) -> Result<(), String>{
+        let a = self.solution()?;
+        let Some(canvas) = a.get_canvas_mut() else {
+            return Err(String::from("Solution does not expose a canvas"));
+        };
+        print!("{}", canvas);
+        Ok(())
+    }
+
+    fn execute(&mut self, words: &[&str]) -> Result<bool, String>{
+        match words {
+            [] => {},
+            ["help"] => println!("{}", HELP),
+            ["quit"] | ["exit"] => return Ok(false),
+            ["load", year, day] => {
+                self.year = year.parse::<u16>().map_err(|_| format!("Invalid year {}", year))?;
+                self.day = day.parse::<u8>().map_err(|_| format!("Invalid day {}", day))?;
+                self.load()?;
+            }
+            ["example"] => {
+                self.test_mode = true;
+                self.load()?;
+            }
+            ["real"] => {
+                self.test_mode = false;
+                self.load()?;
+            }
+            ["reload"] => self.load()?,
+            ["bruteforce"] => {
+                self.bruteforce = !self.bruteforce;
+                println!("Bruteforce {}", if self.bruteforce {"on"} else {"off"});
+                self.load()?;
+            }
+            ["info"] => {
+                if let Err(msg) = self.solution()?.info() {println!("{}", msg)};
+            }
+            ["p1"] => self.run_part(1)?,
+            ["p2"] => self.run_part(2)?,
+            ["run"] => {
+                self.run_part(1)?;
+                self.run_part(2)?;
+            }
+            ["time", part] | ["time", part, _] => {
+                let part = match *part {
+                    "1" | "p1" => 1,
+                    "2" | "p2" => 2,
+                    _ => return Err(format!("Unknown part {}", part))
+                };
+                let n_iterations = match words.get(2) {
+                    Some(n) => n.parse::<u32>().map_err(|_| format!("Invalid number of iterations {}", n))?,
+                    None => 10
+                };
+                self.time_part(part, n_iterations.max(1))?;
+            }
+            ["canvas"] => self.render_canvas()?,
+            ["status"] => {
+                println!("{} day {}, {} input, bruteforce {}, {}",
+                         self.year, self.day,
+                         if self.test_mode {"example"} else {"real"},
+                         if self.bruteforce {"on"} else {"off"},
+                         if self.solution.is_some() {"loaded"} else {"not loaded"});
+            }
+            _ => return Err(format!("Unknown command '{}', type help", words.join(" ")))
+        }
+        Ok(true)
+    }
+}
+
+pub(crate) fn repl(year: u16, day: u8, test_mode: bool, bruteforce: bool){
+    let mut session = Session{
+        year,
+        day,
+        test_mode,
+        bruteforce,
+        solution: None
+    };
+    println!("Advent of Code repl, type help for the list of commands");
+
+    //panics are reported as command errors, the default hook would print them a second time
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("aoc {}/{:0>2}> ", session.year, session.day);
+        let _ = io::stdout().flush();
+        let Some(Ok(line)) = lines.next() else { break };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match session.execute(&words) {
+            Ok(true) => {},
+            Ok(false) => break,
+            Err(msg) => println!("{}", msg)
+        }
+    }
+    panic::set_hook(default_hook);
+}
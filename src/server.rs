@@ -0,0 +1,205 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Instant;
+use serde::Serialize;
+use crate::records::AnswerStore;
+use crate::utils::{input_fingerprint, set_check_expected};
+use crate::{collect_solutions, panic_message, YEARS};
+
+#[derive(Serialize)]
+struct SolutionEntry {
+    year: u16,
+    day: u8
+}
+
+#[derive(Serialize)]
+struct PartResponse {
+    answer: Option<String>,
+    error: Option<String>,
+    time_ns: u64
+}
+
+#[derive(Serialize)]
+struct SolveResponse {
+    year: u16,
+    day: u8,
+    fingerprint: String,
+    parse_time_ns: u64,
+    part1: PartResponse,
+    part2: PartResponse
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: String
+}
+
+//puzzle inputs are a few tens of kilobytes, anything much larger is refused unread
+const MAX_BODY_BYTES: usize = 1 << 20;
+
+enum RequestError {
+    Malformed(io::Error),
+    TooLarge(usize)
+}
+
+impl From<io::Error> for RequestError {
+    fn from(e: io::Error) -> Self {
+        RequestError::Malformed(e)
+    }
+}
+
+struct Response {
+    status: &'static str,
+    body: String
+}
+
+impl Response {
+    fn json<T: Serialize>(status: &'static str, value: &T) -> Self{
+        Self{
+            status,
+            body: serde_json::to_string(value).unwrap_or_else(|_| String::from("{}"))
+        }
+    }
+
+    fn error(status: &'static str, msg: String) -> Self{
+        Self::json(status, &ErrorResponse{error: msg})
+    }
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request, RequestError>{
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<usize>()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Err(RequestError::TooLarge(content_length));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8(body)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(Request{method, path, body})
+}
+
+fn write_response(mut stream: &TcpStream, response: Response) -> io::Result<()>{
+    write!(stream,
+           "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+           response.status, response.body.len(), response.body)?;
+    stream.flush()
+}
+
+fn list_solutions() -> Response{
+    let mut entries: Vec<SolutionEntry> = Vec::new();
+    for year in YEARS {
+        let mut days: Vec<u8> = collect_solutions(year).into_keys().collect();
+        days.sort_unstable();
+        entries.extend(days.into_iter().map(|day| SolutionEntry{year, day}));
+    }
+    Response::json("200 OK", &entries)
+}
+
+fn solve(year: u16, day: u8, input: &str) -> Response{
+    if !YEARS.contains(&year) {
+        return Response::error("404 Not Found", format!("No solutions for {}", year));
+    }
+    let mut solutions = collect_solutions(year);
+    let Some(mut a) = solutions.remove(&day) else {
+        return Response::error("404 Not Found", format!("No solution for {} day {}", year, day));
+    };
+
+    let fingerprint = input_fingerprint(input);
+    let is_reference = AnswerStore::detached(year).is_reference(day, &fingerprint);
+    set_check_expected(is_reference);
+    a.get_label_mut().fingerprint = Some(fingerprint.clone());
+
+    let start = Instant::now();
+    match panic::catch_unwind(AssertUnwindSafe(|| a.read_input_from_str(input))) {
+        Ok(Ok(())) => {},
+        Ok(Err(e)) => return Response::error("422 Unprocessable Entity", format!("Cannot parse input: {}", e)),
+        Err(payload) => return Response::error("422 Unprocessable Entity", format!("Cannot parse input: {}", panic_message(payload)))
+    }
+    let parse_time_ns = start.elapsed().as_nanos() as u64;
+
+    let [part1, part2] = [1, 2].map(|part| {
+        let start = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| match part {
+            1 => a.compute_part1_answer(false),
+            _ => a.compute_part2_answer(false)
+        }));
+        let time_ns = start.elapsed().as_nanos() as u64;
+        match result {
+            Ok(Ok(answer)) => PartResponse{answer: Some(answer), error: None, time_ns},
+            Ok(Err(msg)) => PartResponse{answer: None, error: Some(msg), time_ns},
+            Err(payload) => PartResponse{answer: None, error: Some(format!("panic: {}", panic_message(payload))), time_ns}
+        }
+    });
+
+    Response::json("200 OK", &SolveResponse{year, day, fingerprint, parse_time_ns, part1, part2})
+}
+
+fn route(request: &Request) -> Response{
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["solutions"]) => list_solutions(),
+        ("POST", ["solve", year, day]) => {
+            match (year.parse::<u16>(), day.parse::<u8>()) {
+                (Ok(year), Ok(day)) => solve(year, day, &request.body),
+                _ => Response::error("400 Bad Request", format!("Invalid year or day in {}", request.path))
+            }
+        }
+        _ => Response::error("404 Not Found", format!("Unknown endpoint {} {}", request.method, request.path))
+    }
+}
+
+fn handle_connection(stream: TcpStream){
+    let response = match read_request(&stream) {
+        Ok(request) => route(&request),
+        Err(RequestError::Malformed(e)) => Response::error("400 Bad Request", format!("Malformed request: {}", e)),
+        Err(RequestError::TooLarge(length)) => Response::error("413 Payload Too Large",
+            format!("Request body of {} bytes exceeds the limit of {} bytes", length, MAX_BODY_BYTES))
+    };
+    if let Err(e) = write_response(&stream, response) {
+        println!("Cannot send response: {}", e);
+    }
+}
+
+//GET  /solutions               -> [{"year": 2024, "day": 1}, ...]
+//POST /solve/{year}/{day}      -> answers and timings of both parts for the input in the request body
+pub(crate) fn serve(address: &str) -> io::Result<()>{
+    let listener = TcpListener::bind(address)?;
+    println!("Serving solutions on http://{}", address);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                thread::spawn(move || handle_connection(stream));
+            }
+            Err(e) => println!("Connection failed: {}", e)
+        }
+    }
+    Ok(())
+}
@@ -1,9 +1,15 @@
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::fmt::{Debug, Display};
 use std::fs::File;
 use std::io::{self, BufRead, Write};
 use std::path::Path;
-use crate::geometry::{Canvas, CanvasAsync};
+use crate::geometry::Canvas;
+
+mod range_set;
+
+#[allow(unused_imports)]
+pub(crate) use range_set::{RangeMap, RangeSet};
 
 #[macro_export]
 macro_rules! hashset{
@@ -21,10 +27,21 @@ macro_rules! hashset{
 
 pub(crate) const PUZZLE_INPUT: &str  = "input/";
 
+thread_local! {
+    //hard-coded answers only hold for the reference input of a day
+    static CHECK_EXPECTED: Cell<bool> = const { Cell::new(true) };
+}
+
+pub fn set_check_expected(check: bool){
+    CHECK_EXPECTED.with(|c| c.set(check));
+}
+
 pub (crate) struct Label {
     pub(crate) day: u8,
     pub (crate) year: u16,
-    pub(crate) has_input: bool
+    pub(crate) has_input: bool,
+    pub(crate) fingerprint: Option<String>,
+    pub(crate) input_override: Option<String>
 }
 
 
@@ -33,12 +50,17 @@ impl Label {
         Self{
             day,
             year,
-            has_input: false
+            has_input: false,
+            fingerprint: None,
+            input_override: None
         }
     }
 
     pub fn get_puzzle_input_path(&self, test_mode: bool) -> String{
-        if test_mode {
+        if let Some(path) = &self.input_override {
+            path.clone()
+        }
+        else if test_mode {
             PUZZLE_INPUT.to_owned() + format!("{}/day{:0>2}_test.txt", self.year, self.day).as_str()
         }
         else{
@@ -52,14 +74,11 @@ pub(crate) trait Solve
     fn get_label(&self) -> &Label;
     fn get_label_mut(&mut self) -> &mut Label;
 
+    //solutions reading their input as a picture expose the grid to be filled row by row
     fn get_canvas_mut(&mut self) -> Option<&mut Canvas>{
         None
     }
 
-    fn get_canvas_async_mut(&mut self) -> Option<&mut CanvasAsync>{
-        None
-    }
-
     fn apply_bruteforce(&mut self){}
 
     fn check_input(&self, part: Option<u8>) -> Result<(), String> {
@@ -77,14 +96,7 @@ pub(crate) trait Solve
     fn add_record_from_line(&mut self, line : String) -> Result<(), std::num::ParseIntError> {
         match self.get_canvas_mut(){
             None => {
-                match self.get_canvas_async_mut(){
-                    None => {
-                        "invalid".parse::<i32>()?;
-                    },
-                    Some(canvas) => {
-                        canvas.add_row(line.chars().collect());
-                    }
-                };
+                "invalid".parse::<i32>()?;
             },
             Some(canvas) => {
                 canvas.add_row(line.chars().collect());
@@ -97,15 +109,21 @@ pub(crate) trait Solve
     fn read_input(&mut self, test_mode: bool) -> Result<(), std::num::ParseIntError>{
         let filename = self.get_label().get_puzzle_input_path(test_mode);
 
-        if let Ok(lines) = read_lines(filename) {
-            for line in lines.flatten() {
-                self.add_record_from_line(line)?;
-            }
-            self.get_label_mut().has_input = true;
+        if let Ok(content) = std::fs::read_to_string(filename) {
+            self.read_input_from_str(&content)?;
         }
         Ok(())
     }
 
+    //same pipeline as read_input for input which is already in memory
+    fn read_input_from_str(&mut self, content: &str) -> Result<(), std::num::ParseIntError>{
+        for line in content.lines() {
+            self.add_record_from_line(line.to_owned())?;
+        }
+        self.get_label_mut().has_input = true;
+        Ok(())
+    }
+
     fn info(&self) -> Result<(), String> {
         Err(String::from("Advent is missing input"))
     }
@@ -135,27 +153,47 @@ pub fn assert_display<T: Debug+Display+PartialEq>(result: T,
 ) -> Result<String, String>{
     match result_test {
         Some(result_test) => {
-            assert_eq!(result, match test_mode{
-                true =>  result_test,
-                false => result_prd
-            });
+            match test_mode{
+                true =>  assert_eq!(result, result_test),
+                false => if CHECK_EXPECTED.with(|c| c.get()) {assert_eq!(result, result_prd)}
+            }
         },
         None => {
             match test_mode{
                 true =>  {return Err(String::from("Test mode not implemented"));},
-                false => assert_eq!(result, result_prd)
+                false => if CHECK_EXPECTED.with(|c| c.get()) {assert_eq!(result, result_prd)}
             }
         }
     };
     Ok(format!("{}: {}", header, result))
 }
 
+#[allow(dead_code)]
 pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where P: AsRef<Path>, {
     let file = File::open(filename)?;
     Ok(io::BufReader::new(file).lines())
 }
 
+//line endings and trailing newlines do not change the puzzle, so they do not change the fingerprint either
+pub fn normalize_input(raw: &str) -> String{
+    let mut normalized = raw.replace("\r\n", "\n");
+    let trimmed_len = normalized.trim_end_matches('\n').len();
+    normalized.truncate(trimmed_len);
+    normalized.push('\n');
+    normalized
+}
+
+pub fn input_fingerprint(raw: &str) -> String{
+    format!("{:x}", md5::compute(normalize_input(raw).as_bytes()))
+}
+
+pub fn file_fingerprint<P>(filename: P) -> io::Result<String>
+where P: AsRef<Path>, {
+    let raw = std::fs::read_to_string(filename)?;
+    Ok(input_fingerprint(&raw))
+}
+
 pub fn line2vec_i32(line: String) -> Result<Vec<i32>, std::num::ParseIntError> {
     let mut v: Vec<i32> = Vec::new();
     for p in line.split_whitespace() {
@@ -0,0 +1,287 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+//set of integers stored as disjoint half-open ranges keyed by their start,
+//touching ranges are merged so every range is separated from the next by a gap
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RangeSet {
+    ranges: BTreeMap<i64, i64>,
+    total: i64
+}
+
+#[allow(dead_code)]
+impl RangeSet {
+    pub(crate) fn new() -> Self{
+        Self::default()
+    }
+
+    //number of integers in the set
+    pub(crate) fn len(&self) -> i64{
+        self.total
+    }
+
+    pub(crate) fn is_empty(&self) -> bool{
+        self.ranges.is_empty()
+    }
+
+    //number of disjoint ranges
+    pub(crate) fn n_ranges(&self) -> usize{
+        self.ranges.len()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Range<i64>> + '_{
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    //ranges ending after value, starting with the one containing it if any
+    pub(crate) fn iter_from(&self, value: i64) -> impl Iterator<Item = Range<i64>> + '_{
+        let first = self.covering(value).map_or(value, |r| r.start);
+        self.ranges.range(first..).map(|(&start, &end)| start..end)
+    }
+
+    pub(crate) fn first(&self) -> Option<i64>{
+        self.ranges.first_key_value().map(|(&start, _)| start)
+    }
+
+    pub(crate) fn last(&self) -> Option<i64>{
+        self.ranges.last_key_value().map(|(_, &end)| end - 1)
+    }
+
+    //the range containing value
+    pub(crate) fn covering(&self, value: i64) -> Option<Range<i64>>{
+        self.ranges.range(..=value).next_back()
+            .filter(|(_, &end)| end > value)
+            .map(|(&start, &end)| start..end)
+    }
+
+    pub(crate) fn contains(&self, value: i64) -> bool{
+        self.covering(value).is_some()
+    }
+
+    pub(crate) fn contains_range(&self, range: &Range<i64>) -> bool{
+        range.is_empty() || self.covering(range.start).is_some_and(|r| r.end >= range.end)
+    }
+
+    pub(crate) fn insert(&mut self, range: Range<i64>){
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+        //a range starting before may touch or overlap the new one
+        if let Some((&s, &e)) = self.ranges.range(..start).next_back() {
+            if e >= start {
+                start = s;
+                end = end.max(e);
+                self.ranges.remove(&s);
+                self.total -= e - s;
+            }
+        }
+        //every range starting inside or right at the end is swallowed
+        while let Some((&s, &e)) = self.ranges.range(start..=end).next() {
+            end = end.max(e);
+            self.ranges.remove(&s);
+            self.total -= e - s;
+        }
+        self.ranges.insert(start, end);
+        self.total += end - start;
+    }
+
+    pub(crate) fn insert_value(&mut self, value: i64){
+        self.insert(value..value + 1);
+    }
+
+    pub(crate) fn remove(&mut self, range: Range<i64>){
+        if range.is_empty() {
+            return;
+        }
+        //a range starting before may stick into the removed one from the left
+        if let Some((&s, &e)) = self.ranges.range(..range.start).next_back() {
+            if e > range.start {
+                self.ranges.insert(s, range.start);
+                self.total -= e - range.start;
+                if e > range.end {
+                    self.ranges.insert(range.end, e);
+                    self.total += e - range.end;
+                }
+            }
+        }
+        while let Some((&s, &e)) = self.ranges.range(range.start..range.end).next() {
+            self.ranges.remove(&s);
+            self.total -= e - s;
+            if e > range.end {
+                self.ranges.insert(range.end, e);
+                self.total += e - range.end;
+            }
+        }
+    }
+
+    pub(crate) fn remove_value(&mut self, value: i64){
+        self.remove(value..value + 1);
+    }
+
+    pub(crate) fn union(&self, other: &RangeSet) -> RangeSet{
+        let (mut result, smaller) = if self.n_ranges() >= other.n_ranges() {
+            (self.clone(), other)
+        } else {
+            (other.clone(), self)
+        };
+        for r in smaller.iter() {
+            result.insert(r);
+        }
+        result
+    }
+
+    pub(crate) fn intersection(&self, other: &RangeSet) -> RangeSet{
+        let mut result = RangeSet::new();
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        while let (Some(ra), Some(rb)) = (a.peek(), b.peek()) {
+            let (start, end) = (ra.start.max(rb.start), ra.end.min(rb.end));
+            if start < end {
+                result.ranges.insert(start, end);
+                result.total += end - start;
+            }
+            //the range ending first cannot meet anything further on
+            if ra.end < rb.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        result
+    }
+
+    pub(crate) fn difference(&self, other: &RangeSet) -> RangeSet{
+        let mut result = self.clone();
+        for r in other.iter() {
+            result.remove(r);
+        }
+        result
+    }
+
+    //uncovered stretches of bounds, in increasing order
+    pub(crate) fn gaps(&self, bounds: Range<i64>) -> impl Iterator<Item = Range<i64>> + '_{
+        let mut cursor = bounds.start;
+        let end = bounds.end;
+        self.iter_from(bounds.start)
+            .take_while(move |r| r.start < end)
+            .map(Some)
+            .chain(std::iter::once(None))
+            .filter_map(move |r| {
+                let gap_end = r.as_ref().map_or(end, |r| r.start.min(end));
+                let gap = cursor..gap_end;
+                if let Some(r) = r {
+                    cursor = cursor.max(r.end);
+                }
+                Some(gap).filter(|g| !g.is_empty())
+            })
+    }
+
+    pub(crate) fn complement(&self, bounds: Range<i64>) -> RangeSet{
+        self.gaps(bounds).collect()
+    }
+}
+
+impl FromIterator<Range<i64>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        for r in iter {
+            set.insert(r);
+        }
+        set
+    }
+}
+
+impl fmt::Display for RangeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, r) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}..{}", r.start, r.end)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+//piecewise shift of integers: every source range is moved onto a destination range of the same length,
+//values outside all source ranges map onto themselves
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RangeMap {
+    //source start -> (source end, destination start)
+    shifts: BTreeMap<i64, (i64, i64)>
+}
+
+#[allow(dead_code)]
+impl RangeMap {
+    pub(crate) fn new() -> Self{
+        Self::default()
+    }
+
+    pub(crate) fn len(&self) -> usize{
+        self.shifts.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool{
+        self.shifts.is_empty()
+    }
+
+    //later insertions override earlier ones where the source ranges overlap
+    pub(crate) fn insert(&mut self, source: Range<i64>, destination: i64){
+        if source.is_empty() {
+            return;
+        }
+        self.cut(&source);
+        self.shifts.insert(source.start, (source.end, destination));
+    }
+
+    fn cut(&mut self, range: &Range<i64>){
+        if let Some((&s, &(e, d))) = self.shifts.range(..range.start).next_back() {
+            if e > range.start {
+                self.shifts.insert(s, (range.start, d));
+                if e > range.end {
+                    self.shifts.insert(range.end, (e, d + range.end - s));
+                }
+            }
+        }
+        while let Some((&s, &(e, d))) = self.shifts.range(range.start..range.end).next() {
+            self.shifts.remove(&s);
+            if e > range.end {
+                self.shifts.insert(range.end, (e, d + range.end - s));
+            }
+        }
+    }
+
+    pub(crate) fn map(&self, value: i64) -> i64{
+        match self.shifts.range(..=value).next_back() {
+            Some((&s, &(e, d))) if e > value => d + value - s,
+            _ => value
+        }
+    }
+
+    //images of the pieces of range, split wherever the shift changes
+    pub(crate) fn map_range(&self, range: Range<i64>) -> Vec<Range<i64>>{
+        let mut pieces = Vec::new();
+        let mut cursor = range.start;
+        let first = self.shifts.range(..=range.start).next_back()
+            .filter(|(_, &(e, _))| e > range.start)
+            .map_or(range.start, |(&s, _)| s);
+        for (&s, &(e, d)) in self.shifts.range(first..range.end) {
+            if s > cursor {
+                pieces.push(cursor..s);
+            }
+            let (from, to) = (s.max(cursor), e.min(range.end));
+            pieces.push(d + from - s..d + to - s);
+            cursor = to;
+        }
+        if cursor < range.end {
+            pieces.push(cursor..range.end);
+        }
+        pieces
+    }
+
+    pub(crate) fn map_set(&self, set: &RangeSet) -> RangeSet{
+        set.iter().flat_map(|r| self.map_range(r)).collect()
+    }
+}
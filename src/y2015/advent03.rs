@@ -1,27 +1,46 @@
-use std::collections::HashSet;
-use crate::geometry::{Direction, Point2D};
+use crate::geometry::{Direction, Orientation, Point2D, SparseGrid};
 use crate::utils::{assert_display, Label, Solve};
 
 pub(crate) struct Advent {
     label: Label,
-    instructions: String
+    instructions: Vec<Direction>
 }
 impl Default for Advent {
     fn default() -> Self{
         Self{
             label: Label::new(3, 2015),
-            instructions: String::new()
+            instructions: Vec::new()
         }
     }
 }
 
+impl Advent {
+    //presents delivered to each house, the deliverers take turns following the instructions
+    fn deliver(&self, n_deliverers: usize) -> SparseGrid<usize>{
+        let mut visits = SparseGrid::new(0);
+        let mut positions = vec![Point2D::origin(); n_deliverers];
+        *visits.get_mut(&Point2D::origin()) += n_deliverers;
+        for (i, d) in self.instructions.iter().enumerate(){
+            let p = &mut positions[i % n_deliverers];
+            //the arrows are compass points, so north grows y as on a map
+            *p = *p + d.to_point_in(Orientation::Math);
+            *visits.get_mut(p) += 1;
+        }
+        visits
+    }
+}
+
 impl Solve for Advent {
 
     fn get_label(&self) -> &Label{ &self.label }
     fn get_label_mut(&mut self) -> &mut Label {&mut self.label}
 
     fn add_record_from_line(&mut self, line: String) -> Result<(), std::num::ParseIntError>{
-        self.instructions = line;
+        let Some(instructions) = line.chars().map(|ch| Direction::try_from_char(&ch)).collect() else {
+            "invalid".parse::<i32>()?;
+            return Ok(());
+        };
+        self.instructions = instructions;
         Ok(())
     }
 
@@ -33,31 +52,13 @@ impl Solve for Advent {
 
     fn compute_part1_answer(&self, _: bool) -> Result<String, String>{
         self.check_input(Some(1))?;
-        let mut visited: HashSet<Point2D> = HashSet::new();
-        let mut p = Point2D::new(0,0);
-        visited.insert(p.clone());
-        for ch in self.instructions.chars(){
-            p = &p + &Direction::from_char(&ch);
-            visited.insert(p.clone());
-        }
-        assert_display(visited.len(), None, 2565, "Number of houses", false)
+        let visits = self.deliver(1);
+        assert_display(visits.len(), None, 2565, "Number of houses", false)
     }
 
     fn compute_part2_answer(&self,  _: bool) -> Result<String, String>{
         self.check_input(Some(2))?;
-        let mut visited: HashSet<Point2D> = HashSet::new();
-        let mut p1 = Point2D::new(0,0);
-        let mut p2 = Point2D::new(0,0);
-        visited.insert(p1.clone());
-        for (i, ch) in self.instructions.chars().enumerate(){
-            if i%2 == 0 {
-                p1 = &p1 + &Direction::from_char(&ch);
-                visited.insert(p1.clone());
-            }else{
-                p2 = &p2 + &Direction::from_char(&ch);
-                visited.insert(p2.clone());
-            }
-        }
-        assert_display(visited.len(), None, 2639, "Number of houses", false)
+        let visits = self.deliver(2);
+        assert_display(visits.len(), None, 2639, "Number of houses", false)
     }
 }
\ No newline at end of file
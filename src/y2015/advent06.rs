@@ -1,4 +1,4 @@
-use crate::geometry::Point2D;
+use crate::geometry::{subtract_all, Point2D, Rect};
 use crate::utils::{assert_display, Label, Solve};
 
 enum Action{
@@ -9,7 +9,7 @@ enum Action{
 
 pub(crate) struct Advent {
     label: Label,
-    instructions: Vec<(Action, Point2D, Point2D)>,
+    instructions: Vec<(Action, Rect)>,
 }
 impl Default for Advent {
     fn default() -> Self{
@@ -38,7 +38,7 @@ impl Solve for Advent {
                     (p1.split_once(","), p2.split_once(",")){
                     let p1 = Point2D::new(x1.parse::<usize>()?, y1.parse::<usize>()?);
                     let p2 = Point2D::new(x2.parse::<usize>()?, y2.parse::<usize>()?);
-                    self.instructions.push((action, p1, p2));
+                    self.instructions.push((action, Rect::new(p1, p2)));
                 }
             }
         }
@@ -53,37 +53,34 @@ impl Solve for Advent {
 
     fn compute_part1_answer(&self, _: bool) -> Result<String, String>{
         self.check_input(Some(1))?;
-        let mut lights = [[false; 1000]; 1000];
-        for (action, from, to) in self.instructions.iter() {
-            for x in *from.x() as usize..=*to.x() as usize{
-                for y in *from.y() as usize..=*to.y() as usize{
-                    match action {
-                        Action::TurnOn => {
-                            lights[x][y]=true;
-                        },
-                        Action::TurnOff => {
-                            lights[x][y]=false;
-                        },
-                        Action::Toggle => {
-                            lights[x][y]=!lights[x][y]
-                        }
-                    };
+        //lit area is kept as disjoint rectangles instead of toggling cells one by one
+        let mut lit: Vec<Rect> = Vec::new();
+        for (action, rect) in self.instructions.iter() {
+            lit = match action {
+                Action::TurnOn => {
+                    let mut lit = subtract_all(&lit, rect);
+                    lit.push(*rect);
+                    lit
+                },
+                Action::TurnOff => subtract_all(&lit, rect),
+                Action::Toggle => {
+                    let unlit = lit.iter().fold(vec![*rect], |pieces, l| subtract_all(&pieces, l));
+                    let mut lit = subtract_all(&lit, rect);
+                    lit.extend(unlit);
+                    lit
                 }
-            }
-
+            };
         }
-        let result = lights.iter()
-            .map(|x|
-                {x.iter().map(|y| {if *y==true {1} else{0}}).sum::<usize>()}).sum::<usize>();
+        let result = lit.iter().map(|r| r.area()).sum::<usize>();
         assert_display(result, None, 569999, "Number of lights on", false)
     }
 
     fn compute_part2_answer(&self,  _: bool) -> Result<String, String>{
         self.check_input(Some(2))?;
         let mut lights = vec![vec![0usize; 1000]; 1000];
-        for (action, from, to) in self.instructions.iter() {
-            for x in *from.x() as usize..=*to.x() as usize{
-                for y in *from.y() as usize..=*to.y() as usize{
+        for (action, rect) in self.instructions.iter() {
+            for x in rect.range(0).map(|x| x as usize){
+                for y in rect.range(1).map(|y| y as usize){
                     match action {
                         Action::TurnOn => {
                             lights[x][y]+=1;
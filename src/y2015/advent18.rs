@@ -1,6 +1,4 @@
-use std::rc::Rc;
-use crate::geometry::{Canvas, Direction, Point2D};
-use crate::hashset;
+use crate::geometry::{Automaton, Canvas, Image, Palette, Point2D, Recorder, Rule, BLACK};
 use crate::utils::{assert_display, Label, Solve};
 
 pub(crate) struct Advent {
@@ -25,68 +23,21 @@ impl Advent {
         self.check_input(Some(part))?;
         let (&width, &height) = self.canvas.shape();
 
-        let (corners, mut canvas) = if stuck_corners {
-            let corners = hashset!(Point2D::new(0,0),
-                Point2D::new(0, height-1),
-                Point2D::new(width-1, 0),
-                Point2D::new(width-1, height-1)
-            );
-            let mut canvas = Canvas::default();
-            for y in 0..height {
-                let row = (0..width)
-                    .map(|x| {
-                        let p = Point2D::new(x, y);
-                        let el = *self.canvas.get_element(&p).unwrap();
-                        if corners.contains(&p) {
-                            '#'
-                        } else {
-                            el
-                        }
-                    }
-                    ).collect();
-                canvas.add_row(row);
+        let mut lights = Automaton::from_grid(&self.canvas, |&el| el == '#', Rule::life());
+        if stuck_corners {
+            for corner in [Point2D::new(0, 0), Point2D::new(0, height - 1), Point2D::new(width - 1, 0), Point2D::new(width - 1, height - 1)] {
+                lights = lights.pin(&corner, true);
             }
-            (corners, canvas)
-        }else{
-            (hashset!(), self.canvas.clone())
-        };
+        }
 
+        let palette = Palette::new(BLACK).with(true, [255, 220, 0]);
+        let mut recorder = Recorder::new(self.label.year, &format!("lights_part{}", part));
+        recorder.record(|| Image::from_grid(&lights.to_grid(), |el| palette.color(el)));
         for _ in 0..100 {
-            let mut next_canvas = Canvas::default();
-
-            for y in 0..height {
-                let row = (0..width)
-                    .map(|x| {
-                        let p = Point2D::new(x, y);
-                        let el = canvas.get_element(&p).unwrap();
-                        if corners.contains(&p){
-                            '#'
-                        }
-                        else {
-                            let count_on = [&Direction::base()[..], &Direction::diagonal()[..]].concat()
-                                .iter()
-                                .filter_map(|d| canvas.get_element(&(&p + d)))
-                                .filter(|&&el| el == '#' || corners.contains(&p) )
-                                .count();
-
-                            match el {
-                                '#' if (2..=3).contains(&count_on) => '#',
-                                '.' if count_on == 3 => '#',
-                                _ => '.',
-                            }
-                        }
-                    })
-                    .collect();
-
-                next_canvas.add_row(row);
-            }
-
-            canvas = next_canvas;
+            lights.step();
+            recorder.record(|| Image::from_grid(&lights.to_grid(), |el| palette.color(el)));
         }
-        let result = match canvas.elements().get(&Rc::new('#')){
-            None => 0,
-            Some(points) => points.len()
-        };
+        let result = lights.population();
         assert_display(result, None, result_prd, "Number of lights", false)
     }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use crate::math::divisor_sums;
 use crate::utils::{assert_display, Label, Solve};
 
 pub(crate) struct Advent {
@@ -38,57 +39,21 @@ impl Solve for Advent {
     }
     fn compute_part1_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(1))?;
-        let mut n_houses = 1;
-        while max_presents(n_houses)<self.number{
-            n_houses*=10;
-        }
-        let min_number: usize;
-        if self.use_bruteforce {
-            let mut i = n_houses / 10;
-
-            loop {
-                let res = get_house_presents(i);
-                if res >= self.number {
-                    min_number = i;
-                    break;
-                }
-                i+=1;
-            }
+        let min_number = if self.use_bruteforce {
+            (1..).find(|&i| get_house_presents(i) >= self.number).unwrap()
         }
         else {
-            let mut n_houses_upper = n_houses;
-            let mut n_houses_lower = n_houses/10;
-            let mut n_houses_med = (n_houses_upper+n_houses_lower)/2;
-            loop {
-                let result = max_presents(n_houses_med);
-
-                if result > self.number {
-                    n_houses_upper = n_houses_med;
-                    n_houses_med = (n_houses_upper + n_houses_lower) / 2;
-                } else if result < self.number {
-                    n_houses_lower = n_houses_med;
-                    n_houses_med = (n_houses_upper + n_houses_lower) / 2;
-                } else {
-                    min_number = n_houses_med;
-                    break;
-                }
-                if n_houses_upper - n_houses_lower==1{
-                    min_number = n_houses_upper;
-                    break;
-                }
-            }
-        }
+            //house n gets at least 10n presents, so the answer is at most number/10
+            let limit = self.number / 10;
+            divisor_sums(limit, None).iter().position(|&s| s * 10 >= self.number).unwrap_or(limit)
+        };
         assert_display(min_number, None, 776160, "First house number", false)
     }
     fn compute_part2_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(2))?;
-        let mut min_number =1;
-        loop{
-            if get_house_presents_capped(min_number)>=self.number{
-                break;
-            }
-            min_number+=1;
-        }
+        //every elf stops after 50 houses
+        let limit = self.number / 11;
+        let min_number = divisor_sums(limit, Some(50)).iter().position(|&s| s * 11 >= self.number).unwrap_or(limit);
         assert_display(min_number, None, 786240, "First house number", false)
     }
 }
@@ -107,39 +72,3 @@ fn get_house_presents(n: usize) -> usize {
     }
     divisors.iter().sum::<usize>()*10
 }
-
-fn get_house_presents_capped(n: usize) -> usize {
-    let mut divisors = HashSet::new();
-    let sqrt_n = (n as f64).sqrt() as usize;
-
-    for i in 1..=sqrt_n {
-        if n % i == 0 {
-            divisors.insert(i); // Add the divisor
-            if i != n / i {
-                divisors.insert(n / i); // Add the complement divisor
-            }
-        }
-    }
-    let mut divisors_passed: HashSet<usize> = HashSet::new();
-    for d in divisors.iter(){
-        if d*50>=n{
-
-            divisors_passed.insert(*d);
-        }
-    }
-    divisors_passed.iter().sum::<usize>()*11
-}
-
-fn max_presents(n_houses:usize)->usize{
-    let mut presents = vec![0; n_houses+1];
-    let mut n = 1;
-    while n<=n_houses{
-        let mut elf = n;
-        while elf<presents.len(){
-            presents[elf]+=n*10;
-            elf+=n;
-        }
-        n+=1;
-    }
-    *presents.iter().max().unwrap()
-}
@@ -1,5 +1,11 @@
 use crate::utils::{assert_display, Label, Solve};
 use regex::Regex;
+use crate::geometry::{CantorDiagonal, Curve, Point2D};
+use crate::math::mod_pow;
+
+const FIRST_CODE: i128 = 20151125;
+const MULTIPLIER: i128 = 252533;
+const MODULUS: i128 = 33554393;
 
 pub(crate) struct Advent {
     label: Label,
@@ -37,15 +43,9 @@ impl Solve for Advent {
     }
     fn compute_part1_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(1))?;
-        let mut value: usize = 20151125;
-        let (mut row, mut col) = (0, 0);
-        loop{
-            (row, col) = next_coordinate(row, col);
-            value = next_code(value);
-            if row ==self.row-1 && col == self.col-1{
-                break;
-            }
-        }
+        //codes are filled diagonal by diagonal, each one multiplying the previous by the same factor
+        let index = CantorDiagonal.index(&Point2D::new(self.col - 1, self.row - 1)).unwrap();
+        let value = FIRST_CODE * mod_pow(MULTIPLIER, index as u64, MODULUS) % MODULUS;
         assert_display(value, None, 2650453, "Code", false)
     }
     fn compute_part2_answer(&self, _test_mode: bool) -> Result<String, String>{
@@ -53,14 +53,3 @@ impl Solve for Advent {
         Ok(String::from("No computation required"))
     }
 }
-
-fn next_code(value: usize)->usize{
-    (value*252533)%33554393
-}
-fn next_coordinate(row:usize, col:usize) -> (usize, usize){
-    if row ==0{
-        (col+1, 0)
-    }else{
-        (row-1, col+1)
-    }
-}
\ No newline at end of file
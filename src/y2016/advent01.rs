@@ -1,10 +1,10 @@
 use std::collections::HashSet;
-use crate::geometry::{Direction, Point2D, Vector};
+use crate::geometry::{Direction, Point2D, Turn, Vector};
 use crate::utils::{assert_display, Label, Solve};
 
 pub(crate) struct Advent {
     label: Label,
-    sequence: Vec<(char, usize)>
+    sequence: Vec<(Turn, usize)>
 }
 impl Default for Advent {
     fn default() -> Self{
@@ -23,29 +23,13 @@ impl Advent {
     ) -> Result<String, String> {
         self.check_input(Some(part))?;
         let mut position = Point2D::new(0,0);
-        let mut direction = Direction::Down;
+        let mut direction = Direction::Up;
         let mut vector: Vector;
         let mut visited: HashSet<Point2D> = HashSet::new();
-        visited.insert(position.clone());
+        visited.insert(position);
         let mut taxicab_distance: Option<usize> = None;
-        for (side, step) in self.sequence.iter(){
-            direction = match side{
-                'R' => match direction{
-                    Direction::Down => Direction::Right,
-                    Direction::Right => Direction::Up,
-                    Direction::Up => Direction::Left,
-                    Direction::Left => Direction::Down,
-                    _ => unreachable!()
-                },
-                'L' => match direction{
-                    Direction::Down => Direction::Left,
-                    Direction::Right => Direction::Down,
-                    Direction::Up => Direction::Right,
-                    Direction::Left => Direction::Up,
-                    _ => unreachable!()
-                }
-                _ => unreachable!()
-            };
+        for (turn, step) in self.sequence.iter(){
+            direction = direction.apply(*turn);
             vector = Vector::new(direction, position);
             if first_visited {
                 for s in 0..*step {
@@ -54,7 +38,7 @@ impl Advent {
                         taxicab_distance = Some((position.x().abs() + position.y().abs()) as usize);
                         break;
                     } else {
-                        visited.insert(position.clone());
+                        visited.insert(position);
                     }
                 }
             }
@@ -82,7 +66,10 @@ impl Solve for Advent {
 
     fn add_record_from_line(&mut self, line: String) -> Result<(), std::num::ParseIntError>{
         for x in  line.split(", "){
-            self.sequence.push((x.chars().nth(0).unwrap(), *(&x[1..].parse::<usize>()?)));
+            match x.chars().next().and_then(|ch| Turn::try_from_char(&ch)) {
+                Some(turn) => self.sequence.push((turn, x[1..].parse::<usize>()?)),
+                None => {"invalid".parse::<i32>()?;}
+            }
         }
         Ok(())
     }
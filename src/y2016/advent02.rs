@@ -1,11 +1,10 @@
-use std::rc::Rc;
 use crate::geometry::{Canvas, Direction};
 use crate::hashset;
 use crate::utils::{assert_display, Label, Solve};
 
 pub(crate) struct Advent {
     label: Label,
-    instructions: Vec<String>
+    instructions: Vec<Vec<Direction>>
 }
 impl Default for Advent {
     fn default() -> Self{
@@ -24,23 +23,16 @@ impl Advent {
     ) -> Result<String, String> {
         self.check_input(Some(part))?;
         let forbidden = hashset![Some(&'.'), None];
-        let mut position = keypad.try_locate_element(&'5')?.clone().pop_first().unwrap();
+        let mut position = *keypad.try_locate_element(&'5')?.first().unwrap();
         let mut code: String = String::new();
         for i in self.instructions.iter(){
-            for mve in i.chars(){
-                let d = match mve{
-                    'U' => Direction::Down,
-                    'D' => Direction::Up,
-                    'R' => Direction::Right,
-                    'L' => Direction::Left,
-                    _ => unreachable!()
-                };
-                let next = &*position + &d;
+            for d in i.iter(){
+                let next = &position + d;
                 if !forbidden.contains(&keypad.get_element(&next)){
-                    position = Rc::new(next);
+                    position = next;
                 }
             }
-            code.push(*keypad.get_element(&*position).unwrap());
+            code.push(keypad[&position]);
         }
         assert_display(code, None, result_prd, "Code", false)
     }
@@ -52,7 +44,11 @@ impl Solve for Advent {
     fn get_label_mut(&mut self) -> &mut Label {&mut self.label}
 
     fn add_record_from_line(&mut self, line: String) -> Result<(), std::num::ParseIntError>{
-        self.instructions.push(line);
+        let Some(instruction) = line.chars().map(|ch| Direction::try_from_char(&ch)).collect() else {
+            "invalid".parse::<i32>()?;
+            return Ok(());
+        };
+        self.instructions.push(instruction);
         Ok(())
     }
 
@@ -1,7 +1,7 @@
-use std::collections::HashMap;
 use std::fs;
 use regex::Regex;
-use crate::utils::{assert_display, write_vec_to_file, Label, Solve};
+use crate::geometry::{Grid, Image, Point2D, Recorder, Rect, Render, Topology, BLACK, WHITE};
+use crate::utils::{assert_display, Label, Solve};
 
 const SCREEN_WIDTH: usize = 50;
 const SCREEN_HEIGHT: usize = 6;
@@ -14,37 +14,13 @@ enum Command{
 }
 
 impl Command{
-    fn apply(&self, screen: &mut HashMap<(usize,usize), bool>){
+    fn apply(&self, screen: &mut Grid<bool>){
         match self{
             Command::Rect(width, height) =>{
-                for i in 0..*width{
-                    for j in 0..*height{
-                        screen.insert((j,i), true);
-                    }
-                }
-            },
-            Command::RotateRow(row_ix,shift) =>{
-                let mut row = [false; SCREEN_WIDTH];
-                for ((i,j), v) in screen.iter(){
-                    if i == row_ix{
-                        row[*j] = *v;
-                    }
-                }
-                for j in 0..SCREEN_WIDTH{
-                    screen.insert((*row_ix, (j+shift)%SCREEN_WIDTH), row[j]);
-                }
+                screen.update(Rect::from_size(*width, *height).points().map(|p| (p, true)));
             },
-            Command::RotateCol(col_ix, shift) =>{
-                let mut col = [false; SCREEN_HEIGHT];
-                for ((i,j), v) in screen.iter(){
-                    if j == col_ix{
-                        col[*i] = *v;
-                    }
-                }
-                for i in 0..SCREEN_HEIGHT{
-                    screen.insert(((i+shift)%SCREEN_HEIGHT, *col_ix), col[i]);
-                }
-            }
+            Command::RotateRow(row_ix,shift) => screen.rotate_row(*row_ix, *shift as isize),
+            Command::RotateCol(col_ix, shift) => screen.rotate_column(*col_ix, *shift as isize)
         }
     }
 }
@@ -100,35 +76,31 @@ impl Solve for Advent {
     }
     fn compute_part1_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(1))?;
-        let mut screen: HashMap<(usize,usize), bool> = HashMap::new();
-        for i in 0..SCREEN_WIDTH {
-            for j in 0..SCREEN_HEIGHT {
-                screen.insert((j,i), false);
-            }
-        }
+        let mut screen = Grid::new(SCREEN_WIDTH, SCREEN_HEIGHT, false).with_topology(Topology::Toroidal);
+        let mut recorder = Recorder::new(self.label.year, "screen");
         for command in &self.commands{
             command.apply(&mut screen);
+            recorder.record_grid(&screen, |&v| if v { WHITE } else { BLACK });
         }
-        let result = screen.iter().filter(|(_, &v)| v).count();
+        let result = screen.values().filter(|&&v| v).count();
 
         //create picture of display for part 2
         let prefix = format!("output/{}/", self.label.year);
         let _ = fs::create_dir_all(prefix.clone());
         let filename = format!("{}code.txt", prefix);
 
-        let mut vec:Vec<Vec<char>> = Vec::new();
-        for j in 0..SCREEN_HEIGHT {
-            let mut row: Vec<char> = Vec::new();
-            for i in 0..SCREEN_WIDTH {
-                if *screen.get(&(j,i)).unwrap(){
-                    row.push('\u{25A0}')
-                }else{
-                    row.push('.');
-                }
-            }
-            vec.push(row);
+        let lit: Vec<Point2D> = screen.iter()
+            .filter(|(_, &v)| v)
+            .map(|(p, _)| p)
+            .collect();
+        if let Some(bounds) = screen.bounds() {
+            let _ = Render::blank(bounds)
+                .overlay(&lit, '\u{25A0}')
+                .write_to_file(&filename);
         }
-        let _ = write_vec_to_file(vec, &filename);
+        recorder.picture("code.png", |filename| {
+            Image::from_grid(&screen, |&v| if v { WHITE } else { BLACK }).scaled(8).write_to_file(filename)
+        });
 
         assert_display(result, None, 110, "Number of lit pixels", false)
     }
@@ -1,6 +1,5 @@
-use std::collections::HashSet;
-use crate::geometry::{Direction, Point2D};
-use crate::hashset;
+use std::collections::VecDeque;
+use crate::geometry::{Direction, GridLike, Point2D, SparseGrid};
 use crate::utils::{assert_display, Label, Solve};
 
 pub(crate) struct Advent {
@@ -26,6 +25,28 @@ impl Advent{
                 .filter(|x| x == &'1').count() % 2 == 0
         }
     }
+
+    //breadth first distances from start over open cells, up to max_steps or until target is reached;
+    //only the cells reached are stored, the maze itself is unbounded
+    fn explore(&self, start: Point2D, max_steps: usize, target: Option<Point2D>) -> SparseGrid<Option<usize>>{
+        let mut distances = SparseGrid::new(None);
+        distances.set(&start, Some(0));
+        let mut queue = VecDeque::from([start]);
+        while let Some(p) = queue.pop_front(){
+            let distance = distances.get(&p).unwrap();
+            if Some(p) == target || distance == max_steps{
+                break;
+            }
+            for d in Direction::base(){
+                let next_p = &p+&d;
+                if !distances.contains(&next_p) && self.is_open(next_p.x(), next_p.y()){
+                    distances.set(&next_p, Some(distance + 1));
+                    queue.push_back(next_p);
+                }
+            }
+        }
+        distances
+    }
 }
 
 impl Solve for Advent {
@@ -48,42 +69,12 @@ impl Solve for Advent {
 
         let start = Point2D::new(1,1);
         let finish = Point2D::new(31, 39);
-        assert_eq!(self.is_open(start.x(), start.y()), true);
-        assert_eq!(self.is_open(finish.x(), finish.y()), true);
+        assert!(self.is_open(start.x(), start.y()));
+        assert!(self.is_open(finish.x(), finish.y()));
 
-        let mut queue = hashset!(start);
-        let mut visited: HashSet<Point2D> = HashSet::new();
-        let mut curr_step = 0;
-        let mut result: Option<usize> = None;
-        loop{
-            let mut next_queue: HashSet<Point2D> = HashSet::new();
-            for p in queue.iter(){
-                for d in Direction::base(){
-                    let next_p = p+&d;
-                    if next_p==finish{
-                        result = Some(curr_step+1);
-                        break;
-                    }
-                    if !visited.contains(&next_p) && self.is_open(next_p.x(), next_p.y()){
-                        next_queue.insert(next_p);
-                        visited.insert(next_p);
-                    }
-                }
-                if result.is_some(){
-                    break;
-                }
-            }
-            if result.is_some(){
-                break;
-            }
-            queue = next_queue;
-            if queue.is_empty(){
-                break;
-            }
-            curr_step+=1;
-        }
-        match result{
-            Some(result) => assert_display(result, None, 86, "Fewest number of steps", false),
+        let distances = self.explore(start, usize::MAX, Some(finish));
+        match distances.get(&finish){
+            Some(result) => assert_display(*result, None, 86, "Fewest number of steps", false),
             None=>Err(String::from("No solution found"))
         }
     }
@@ -91,31 +82,9 @@ impl Solve for Advent {
         self.check_input(Some(2))?;
 
         let start = Point2D::new(1,1);
-        assert_eq!(self.is_open(start.x(), start.y()), true);
+        assert!(self.is_open(start.x(), start.y()));
 
-        let mut queue = hashset!(start);
-        let mut visited: HashSet<Point2D> = HashSet::new();
-        let mut curr_step = 0;
-        loop{
-            if curr_step==50{
-                break;
-            }
-            let mut next_queue: HashSet<Point2D> = HashSet::new();
-            for p in queue.iter(){
-                for d in Direction::base(){
-                    let next_p = p+&d;
-                    if !visited.contains(&next_p) && self.is_open(next_p.x(), next_p.y()){
-                        next_queue.insert(next_p);
-                        visited.insert(next_p);
-                    }
-                }
-            }
-            queue = next_queue;
-            if queue.is_empty(){
-                break;
-            }
-            curr_step+=1;
-        }
-        assert_display(visited.len(), None, 127, "Number of locations", false)
+        let distances = self.explore(start, 50, None);
+        assert_display(distances.len(), None, 127, "Number of locations", false)
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use regex::Regex;
+use crate::math::crt;
 use crate::utils::{assert_display, Label, Solve};
 
 pub(crate) struct Advent {
@@ -22,31 +23,12 @@ impl Advent {
              part: u8
     ) -> Result<String, String> {
         self.check_input(Some(part))?;
-        let max_size = discs.iter().map(|(_, x)| x.0).max().unwrap();
-        let index_max = discs.iter().filter_map(|(k, x)| if x.0==max_size {Some(k)} else {None}).collect::<Vec<_>>()[0];
-        let mut result: Option<usize> = None;
-        let position_shifted = discs.get(index_max).unwrap().1+*index_max;
-        for c in 0usize..usize::MAX{
-            if max_size*c>=position_shifted{
-                let delay = max_size*c-position_shifted;
-                let mut delay_fits = true;
-                for (k, x) in discs.iter(){
-                    if k==index_max{
-                        continue;
-                    }
-                    if (x.1+delay+k)%x.0!=0{
-                        delay_fits = false;
-                        break;
-                    }
-                }
-                if delay_fits{
-                    result = Some(delay);
-                    break;
-                }
-            }
-        }
-        match result{
-            Some(delay) => assert_display(delay, None, result_prd, "Earliest start", false),
+        //disc k reached after delay + k seconds is at slot 0 iff delay = -(position + k) (mod size)
+        let congruences: Vec<(i128, i128)> = discs.iter()
+            .map(|(k, &(size, position))| (-((position + k) as i128), size as i128))
+            .collect();
+        match crt(&congruences){
+            Some((delay, _)) => assert_display(delay as usize, None, result_prd, "Earliest start", false),
             None => Err(String::from("Not solution found"))
         }
     }
@@ -116,8 +116,8 @@ fn is_valid(point: &Point2D)->bool{
 
 fn dir2char(dir: &Direction) -> char{
     match dir{
-        Direction::Down => 'U',
-        Direction::Up => 'D',
+        Direction::Up => 'U',
+        Direction::Down => 'D',
         Direction::Right => 'R',
         Direction::Left => 'L',
         _ => unreachable!()
@@ -128,7 +128,7 @@ fn hash2dir(hash: String)->HashSet<Direction>{
     let values = hash.chars().take(4).collect::<String>();
     let open = hashset!['b','c','d','e','f'];
     let mut result: HashSet<Direction> = HashSet::new();
-    for (v, d ) in izip!(values.chars(), [Direction::Down, Direction::Up, Direction::Left, Direction::Right]){
+    for (v, d ) in izip!(values.chars(), [Direction::Up, Direction::Down, Direction::Left, Direction::Right]){
         if open.contains(&v){
             result.insert(d);
         }
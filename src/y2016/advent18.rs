@@ -1,3 +1,4 @@
+use crate::geometry::{Automaton, Rule};
 use crate::utils::{assert_display, Label, Solve};
 
 pub(crate) struct Advent {
@@ -14,39 +15,23 @@ impl Default for Advent {
 }
 
 impl Advent{
-fn solve(&self,
-         row_limit: usize,
-         result_prd: usize,
-         part: u8
-) -> Result<String, String> {
-    self.check_input(Some(part))?;
-    let n = self.starting_row.len();
-    let mut current_row: Vec<char> = self.starting_row.chars().collect();
-    let mut next_row = vec!['.'; n];
-    let mut n_safe = current_row.iter().filter(|&&x| x == '.').count();
-    let mut n_rows = 1;
-
-    while n_rows < row_limit {
-        let mut n_safe_row = 0;
-        for i in 0..n {
-            let (l, c, r) = (
-                if i > 0 { current_row[i - 1] } else { '.' },
-                current_row[i],
-                if i < n - 1 { current_row[i + 1] } else { '.' },
-            );
-            next_row[i] = if matches!((l, c, r), ('^', '^', '.') | ('.', '^', '^') | ('.', '.', '^') | ('^', '.', '.')) {
-                '^'
-            } else {
-                n_safe_row += 1;
-                '.'
-            };
+    //a tile is a trap when exactly one of the tiles left and right of it in the row above is,
+    //the walls beyond the row count as safe
+    fn solve(&self,
+             row_limit: usize,
+             result_prd: usize,
+             part: u8
+    ) -> Result<String, String> {
+        self.check_input(Some(part))?;
+        let row: Vec<bool> = self.starting_row.chars().map(|ch| ch == '^').collect();
+        let mut traps = Automaton::from_row(&row, Rule::elementary(|left, _, right| left != right));
+        let mut n_safe = 0;
+        for _ in 0..row_limit {
+            n_safe += traps.width() - traps.population();
+            traps.step();
         }
-        n_safe += n_safe_row;
-        std::mem::swap(&mut current_row, &mut next_row);
-        n_rows += 1;
+        assert_display(n_safe, None, result_prd, "Number of safe tiles", false)
     }
-    assert_display(n_safe, None, result_prd, "Number of safe tiles", false)
-}
 }
 
 impl Solve for Advent {
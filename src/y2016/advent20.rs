@@ -1,15 +1,16 @@
-use std::collections::BTreeSet;
-use crate::utils::{assert_display, Label, Solve};
+use crate::utils::{assert_display, Label, RangeSet, Solve};
+
+const ADDRESS_SPACE: i64 = 1 << 32;
 
 pub(crate) struct Advent {
     label: Label,
-    blacklist: BTreeSet<(u32,u32)>
+    blacklist: RangeSet
 }
 impl Default for Advent {
     fn default() -> Self{
         Self{
             label: Label::new(20, 2016),
-            blacklist: BTreeSet::new()
+            blacklist: RangeSet::new()
         }
     }
 }
@@ -21,66 +22,26 @@ impl Solve for Advent {
 
     fn add_record_from_line(&mut self, line: String) -> Result<(), std::num::ParseIntError>{
         if let Some((from, to)) = line.split_once("-"){
-            self.blacklist.insert((from.parse::<u32>()?, to.parse::<u32>()?));
+            self.blacklist.insert(from.parse::<u32>()? as i64..to.parse::<u32>()? as i64 + 1);
         }
         Ok(())
     }
 
     fn info(&self) -> Result<(), String>{
         self.check_input(None)?;
-        println!("List length: {}", self.blacklist.len());
+        println!("Disjoint blocked ranges: {}", self.blacklist.n_ranges());
         Ok(())
     }
     fn compute_part1_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(1))?;
-        let mut candidate = 0u32;
-        let mut result: Option<u32> = None;
-        loop {
-            for (f, t) in self.blacklist.iter() {
-                if f<=&candidate && t>=&candidate{
-                    candidate = t+1;
-                    break;
-                }
-                if f>&candidate{
-                    result = Some(candidate);
-                    break;
-                }
-            }
-            if result.is_some(){
-                break;
-            }
-        }
-        assert_display(result.unwrap(), None, 32259706, "Lowest allowed IP", false)
+        let result = self.blacklist.gaps(0..ADDRESS_SPACE).next()
+            .map(|gap| gap.start)
+            .ok_or("Every IP is blocked".to_string())?;
+        assert_display(result, None, 32259706, "Lowest allowed IP", false)
     }
     fn compute_part2_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(2))?;
-        let mut candidate = 0u32;
-        let mut n_allowed = 0;
-        let max_limit = self.blacklist.iter().map(|(_, t)| *t).max().unwrap();
-        while candidate<max_limit {
-            for (f, t) in self.blacklist.iter() {
-                if f<=&candidate && t>=&candidate{
-                    candidate = t+1;
-                    break;
-                }
-                if f>&candidate{
-                    n_allowed+=f-candidate;
-                    if t < &u32::MAX {
-                        candidate = t + 1;
-                    }else{
-                        candidate = u32::MAX;
-                    }
-                    break;
-                }
-            }
-        }
-        if candidate<u32::MAX {
-            n_allowed += u32::MAX - (candidate+1);
-        }else{
-            if max_limit<u32::MAX{
-                n_allowed +=1
-            }
-        }
+        let n_allowed = self.blacklist.complement(0..ADDRESS_SPACE).len();
         assert_display(n_allowed, None, 113, "Number of allowed IPs", false)
     }
-}
\ No newline at end of file
+}
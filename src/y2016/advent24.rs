@@ -1,6 +1,5 @@
 use std::cmp::Reverse;
 use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
-use std::rc::Rc;
 use crate::geometry::{Canvas, Direction};
 use crate::utils::{assert_display, Label, Solve};
 
@@ -18,29 +17,29 @@ impl Default for Advent {
 }
 
 impl Advent{
-    fn compute_distances(&self, elements: BTreeSet<Rc<char>> )->Result<HashMap<(char, char), usize>,String>{
+    fn compute_distances(&self, elements: BTreeSet<char> )->Result<HashMap<(char, char), usize>,String>{
         let blocks = self.canvas.try_locate_element(&'#')?;
 
         let num_locations: HashSet<_> = self.canvas.elements()
             .iter()
-            .filter(|(k, _)| !matches!(***k, '#' | '.'))
+            .filter(|(k, _)| !matches!(**k, '#' | '.'))
             .flat_map(|(_, v)| v.iter().cloned())
             .collect();
 
         let mut pair_distances: HashMap<(char, char), usize> = HashMap::new();
         for el in &elements {
-            let from = self.canvas.try_locate_element(&el)?.into_iter().next().unwrap();
-            let mut visited = HashSet::from([from.clone()]);
-            let mut queue = VecDeque::from([(from.clone(), 0)]);
+            let from = *self.canvas.try_locate_element(el)?.first().unwrap();
+            let mut visited = HashSet::from([from]);
+            let mut queue = VecDeque::from([(from, 0)]);
 
             while let Some((p, step)) = queue.pop_front() {
                 for d in Direction::base() {
                     let p_next = &p + &d;
-                    if !blocks.contains(&p_next) && visited.insert(p_next.clone()) {
+                    if !blocks.contains(&p_next) && visited.insert(p_next) {
                         if num_locations.contains(&p_next) {
-                            let ch = *self.canvas.get_element(&*p_next).unwrap();
-                            pair_distances.insert((**el, ch), step + 1);
-                            pair_distances.insert((ch, **el), step + 1);
+                            let ch = self.canvas[&p_next];
+                            pair_distances.insert((*el, ch), step + 1);
+                            pair_distances.insert((ch, *el), step + 1);
                         }
                         queue.push_back((p_next, step + 1));
                     }
@@ -58,11 +57,11 @@ impl Advent{
 
         let elements: BTreeSet<_> = self.canvas.get_element_set()
             .into_iter()
-            .filter(|e| !matches!(**e, '.' | '#'))
+            .filter(|e| !matches!(*e, '.' | '#'))
             .collect();
         let pair_distances = self.compute_distances(elements.clone())?;
 
-        let mut queue = BinaryHeap::from([Reverse((0, '0', elements.iter().filter(|e| ***e != '0').map(|e| **e).collect::<Vec<char>>()))]);
+        let mut queue = BinaryHeap::from([Reverse((0, '0', elements.iter().filter(|e| **e != '0').copied().collect::<Vec<char>>()))]);
         let mut solution = None;
 
         while let Some(Reverse((dist, last, to_visit))) = queue.pop() {
@@ -97,8 +96,8 @@ impl Solve for Advent {
         self.check_input(None)?;
         println!("Canvas shape: {:?}", self.canvas.shape());
         let mut elements = self.canvas.get_element_set();
-        elements.remove(&Rc::new('.'));
-        elements.remove(&Rc::new('#'));
+        elements.remove(&'.');
+        elements.remove(&'#');
         println!("Locations {:?}", elements);
         Ok(())
     }
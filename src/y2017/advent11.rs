@@ -1,9 +1,9 @@
-use crate::geometry::{Direction, Point2D};
+use crate::geometry::{Hex, HexLayout};
 use crate::utils::{assert_display, Label, Solve};
 
 pub(crate) struct Advent {
     label: Label,
-    path: Vec<String>
+    path: Vec<Hex>
 }
 impl Default for Advent {
     fn default() -> Self{
@@ -20,7 +20,12 @@ impl Solve for Advent {
     fn get_label_mut(&mut self) -> &mut Label {&mut self.label}
 
     fn add_record_from_line(&mut self, line: String) -> Result<(), std::num::ParseIntError>{
-        self.path = line.split(",").map(|x| String::from(x)).collect();
+        for step in line.split(",") {
+            match HexLayout::FlatTop.step(step) {
+                Some(offset) => self.path.push(offset),
+                None => {"invalid".parse::<i32>()?;}
+            }
+        }
         Ok(())
     }
 
@@ -31,41 +36,17 @@ impl Solve for Advent {
     }
     fn compute_part1_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(1))?;
-        let mut p = Point2D::new(0, 0);
-        for step in self.path.iter(){
-            p = &p+ &step2dir(step);
-        }
-        assert_display(shortest_path(&p), None, 720, "Fewest number of steps", false)
+        let p: Hex = self.path.iter().sum();
+        assert_display(p.length(), None, 720, "Fewest number of steps", false)
     }
     fn compute_part2_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(2))?;
-        let mut p = Point2D::new(0, 0);
-        let mut furthest_distance = 0usize;
-        for step in self.path.iter(){
-            p = &p+ &step2dir(step);
-            let d = shortest_path(&p);
-            if d>furthest_distance{
-                furthest_distance = d;
-            }
-        }
+        let furthest_distance = self.path.iter()
+            .scan(Hex::origin(), |p, step| {
+                *p = *p + *step;
+                Some(p.length())
+            })
+            .max().unwrap_or(0);
         assert_display(furthest_distance, None, 1485, "Furthest distance", false)
     }
 }
-
-fn step2dir(step: &String) -> Direction{
-    match step.as_str(){
-        "s" => Direction::ToPoint(Point2D::new(0,2)),
-        "sw" => Direction::ToPoint(Point2D::new(-2, 1)),
-        "se" => Direction::ToPoint(Point2D::new(2, 1)),
-        "n" => Direction::ToPoint(Point2D::new(0,-2)),
-        "nw" => Direction::ToPoint(Point2D::new(-2, -1)),
-        "ne" => Direction::ToPoint(Point2D::new(2, -1)),
-        _ => unreachable!()
-    }
-}
-
-fn shortest_path(p: &Point2D) -> usize{
-    let n_diag = p.x().abs()/2;
-    let n_straight = (p.y().abs()-n_diag).abs()/2;
-    (n_diag + n_straight) as usize
-}
\ No newline at end of file
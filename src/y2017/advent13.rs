@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::math::lcm;
 use crate::utils::{assert_display, Label, Solve};
 
 pub(crate) struct Advent {
@@ -28,16 +29,24 @@ impl Advent{
         }
         severity
     }
-    fn passed(&self, delay:usize)->bool{
-        for i in 0..=self.max_layer{
-            let t=i+delay;
-            if let Some(length)=self.layers.get(&i){
-                if on_top(t, *length){
-                   return false
-                }
-            }
+    //layer i catches every delay with delay + i = 0 modulo its period, so the allowed delays
+    //are residue classes; they are refined period by period up to the lcm of all periods
+    fn minimal_delay(&self) -> Option<usize>{
+        let mut forbidden: BTreeMap<i128, HashSet<i128>> = BTreeMap::new();
+        for (&i, &length) in self.layers.iter() {
+            let period = period(length) as i128;
+            forbidden.entry(period).or_default().insert((-(i as i128)).rem_euclid(period));
         }
-        true
+        let (mut modulus, mut allowed) = (1i128, vec![0i128]);
+        for (period, caught) in forbidden.iter() {
+            let next_modulus = lcm(modulus, *period);
+            allowed = allowed.iter()
+                .flat_map(|&r| (0..next_modulus / modulus).map(move |k| r + k * modulus))
+                .filter(|r| !caught.contains(&(r % period)))
+                .collect();
+            modulus = next_modulus;
+        }
+        allowed.into_iter().min().map(|d| d as usize)
     }
 }
 impl Solve for Advent {
@@ -64,21 +73,22 @@ impl Solve for Advent {
     }
     fn compute_part2_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(2))?;
-
-        for delay in 1..= usize::MAX{
-            if self.passed(delay){
-                return assert_display(delay, None, 3923436, "Minimal delay", false)
-            }
+        match self.minimal_delay() {
+            Some(delay) => assert_display(delay, None, 3923436, "Minimal delay", false),
+            None => Err(String::from("No solution found"))
         }
-        Err(String::from("No solution found"))
     }
 }
 
-fn on_top(time:usize, length:usize)->bool{
+fn period(length: usize) -> usize{
     if length == 1{
-        true
+        1
     }else {
-        time % ((length - 1) * 2) == 0
+        (length - 1) * 2
     }
 }
 
+fn on_top(time:usize, length:usize)->bool{
+    time.is_multiple_of(period(length))
+}
+
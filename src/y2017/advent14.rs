@@ -1,5 +1,4 @@
-use std::collections::{HashSet, VecDeque};
-use crate::geometry::{Direction, Point2D};
+use crate::geometry::{Canvas, Connectivity, Image, Labelling, Palette, Recorder, BLACK, WHITE};
 use crate::utils::{assert_display, Label, Solve};
 use crate::y2017::advent10::{knot_hash};
 pub(crate) struct Advent {
@@ -31,41 +30,20 @@ impl Solve for Advent {
     }
     fn compute_part1_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(1))?;
-        let grid = compute_grid(&self.key_string);
-        let used = grid.iter().map(|hb| hb.chars().filter(|x| x == &'1').count()).sum();
+        let disk = compute_disk(&self.key_string);
+        let used = disk.values().filter(|&&ch| ch == '1').count();
+
+        //picture of the disk, used squares in white
+        Recorder::new(self.label.year, "disk").picture("disk.png", |filename| {
+            let palette = Palette::new(BLACK).with('1', WHITE);
+            Image::from_grid(&disk, |ch| palette.color(ch)).scaled(4).write_to_file(filename)
+        });
         assert_display(used, None, 8230, "Number of used squares", false)
     }
     fn compute_part2_answer(&self, _test_mode: bool) -> Result<String, String>{
         self.check_input(Some(2))?;
-        let grid = compute_grid(&self.key_string);
-        let mut used: HashSet<Point2D> = HashSet::new();
-        let n = grid.len();
-        for j in 0..n{
-            for (i, ch) in grid[j].chars().enumerate(){
-                if ch=='1'{
-                    used.insert(Point2D::new(i,j));
-                }
-            }
-        }
-        let mut n_groups = 0usize;
-        while let Some(seed) = used.iter().next(){
-            let mut group: HashSet<Point2D> = HashSet::new();
-            let mut stack: VecDeque<Point2D> = VecDeque::new();
-            stack.push_back(seed.clone());
-            group.insert(seed.clone());
-            while let Some(p) = stack.pop_front(){
-                for d in Direction::base(){
-                    let p_next = &p+&d;
-                    if p_next.is_out_of_bounds(n,n) | group.contains(&p_next) | !used.contains(&p_next){
-                        continue
-                    }
-                    stack.push_back(p_next.clone());
-                    group.insert(p_next);
-                }
-            }
-            n_groups+=1;
-            used = used.difference(&group).cloned().collect();
-        }
+        let disk = compute_disk(&self.key_string);
+        let n_groups = Labelling::new(&disk, Connectivity::Four, |&ch| (ch == '1').then_some(())).len();
         assert_display(n_groups, None, 1103, "Number of groups", false)
     }
 }
@@ -86,4 +64,9 @@ fn compute_grid(key_string: &String)->Vec<String>{
             .map(|ch| format!("{:04b}", ch.to_digit(16).unwrap()))
             .collect::<String>()
     }).collect::<Vec<_>>()
+}
+
+//used squares as '1', free ones as '0'
+fn compute_disk(key_string: &String)->Canvas{
+    Canvas::from_rows(compute_grid(key_string).iter().map(|hb| hb.chars().collect()).collect())
 }
\ No newline at end of file
@@ -1,4 +1,3 @@
-use std::rc::Rc;
 use crate::geometry::{Canvas, Direction, Point2D};
 use crate::utils::{assert_display, vec2line, Label, Solve};
 
@@ -28,17 +27,17 @@ impl Advent {
         self.check_input(Some(part))?;
         let mut curr_element ='|';
         let locs = self.canvas.try_locate_element(&curr_element)?;
-        let mut start: Option<Rc<Point2D>> = None;
+        let mut start: Option<Point2D> = None;
         for el in locs{
             if el.y() == &0{
-                start = Some(el.clone());
+                start = Some(*el);
                 break
             }
         }
         if let Some(p) = start{
             let mut letters: Vec<char> = Vec::new();
-            let mut curr_direction = Direction::Up;
-            let mut curr_point = *p;
+            let mut curr_direction = Direction::Down;
+            let mut curr_point = p;
             let mut n_steps = 1;
             loop{
                 let p_next = &curr_point + &curr_direction;
@@ -47,7 +46,7 @@ impl Advent {
                         '|' | '-' =>{ n_steps+=1; curr_point = p_next},
                         '+' => {
                             match curr_direction{
-                                Direction::Up | Direction::Down => {
+                                Direction::Down | Direction::Up => {
                                     for d in [Direction::Left, Direction::Right]{
                                         let p_test = &p_next + &d;
                                         if let Some(el_test) = self.canvas.get_element(&p_test){
@@ -62,7 +61,7 @@ impl Advent {
                                     }
                                 },
                                 Direction::Left | Direction::Right => {
-                                    for d in [Direction::Up, Direction::Down]{
+                                    for d in [Direction::Down, Direction::Up]{
                                         let p_test = &p_next + &d;
                                         if let Some(el_test) = self.canvas.get_element(&p_test){
                                             if *el_test != curr_element && *el_test!=' '{
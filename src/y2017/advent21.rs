@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use crate::geometry::Canvas;
+use crate::utils::{Label, Solve};
+
+const START: &str = ".#./..#/###";
+
+pub(crate) struct Advent {
+    label: Label,
+    //enhancement rules keyed by the canonical form of their input pattern
+    rules: HashMap<Canvas, Canvas>
+}
+impl Default for Advent {
+    fn default() -> Self{
+        Self{
+            label: Label::new(21, 2017),
+            rules: HashMap::new()
+        }
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Canvas{
+    Canvas::from_rows(pattern.split('/').map(|row| row.chars().collect()).collect())
+}
+
+impl Advent {
+    //every 2x2 or 3x3 square is replaced by the output of the rule matching one of its symmetries
+    fn enhance(&self, image: &Canvas) -> Result<Canvas, String>{
+        let k = if image.width().is_multiple_of(2) { 2 } else { 3 };
+        let enhanced = image.tiles(k).map(|tile| self.rules.get(&tile.canonical()));
+        if enhanced.values().any(|rule| rule.is_none()) {
+            return Err(String::from("No enhancement rule matches the image"));
+        }
+        Ok(Canvas::from_tiles(&enhanced.map(|rule| rule.unwrap().clone())))
+    }
+
+    //number of pixels on after the given number of iterations
+    fn solve(&self, n_iterations: usize, part: u8) -> Result<usize, String>{
+        self.check_input(Some(part))?;
+        let mut image = parse_pattern(START);
+        for _ in 0..n_iterations {
+            image = self.enhance(&image)?;
+        }
+        Ok(image.values().filter(|&&ch| ch == '#').count())
+    }
+}
+
+impl Solve for Advent {
+
+    fn get_label(&self) -> &Label{ &self.label }
+    fn get_label_mut(&mut self) -> &mut Label {&mut self.label}
+
+    fn add_record_from_line(&mut self, line: String) -> Result<(), std::num::ParseIntError>{
+        let Some((input, output)) = line.split_once(" => ") else {
+            "invalid".parse::<i32>()?;
+            return Ok(());
+        };
+        self.rules.insert(parse_pattern(input).canonical(), parse_pattern(output));
+        Ok(())
+    }
+
+    fn info(&self) -> Result<(), String>{
+        self.check_input(None)?;
+        println!("Number of rules: {}", self.rules.len());
+        Ok(())
+    }
+    //the example grows the start pattern once through a 3x3 and once through a 2x2 rule;
+    //the puzzle input is not in the repository, so there is no reference answer to check
+    fn compute_part1_answer(&self, test_mode: bool) -> Result<String, String>{
+        let n_on = self.solve(if test_mode { 2 } else { 5 }, 1)?;
+        if test_mode {
+            assert_eq!(n_on, 12);
+        }
+        Ok(format!("Number of pixels on: {}", n_on))
+    }
+    fn compute_part2_answer(&self, test_mode: bool) -> Result<String, String>{
+        if test_mode {
+            return Err(String::from("Test mode not implemented"));
+        }
+        let n_on = self.solve(18, 2)?;
+        Ok(format!("Number of pixels on: {}", n_on))
+    }
+}
@@ -17,6 +17,7 @@ mod advent16;
 mod advent17;
 mod advent18;
 mod advent19;
+mod advent21;
 
 use std::collections::HashMap;
 use crate::utils::{Solve, add_default_to_collection};
@@ -44,7 +45,7 @@ pub(crate) fn collect_solutions() -> HashMap<u8, Box<dyn Solve>>{
     add_default_to_collection::<advent18::Advent>(&mut advents);
     add_default_to_collection::<advent19::Advent>(&mut advents);
     // add_default_to_collection::<advent20::Advent>(&mut advents);
-    // add_default_to_collection::<advent21::Advent>(&mut advents);
+    add_default_to_collection::<advent21::Advent>(&mut advents);
     // add_default_to_collection::<advent22::Advent>(&mut advents);
     // add_default_to_collection::<advent23::Advent>(&mut advents);
     // add_default_to_collection::<advent24::Advent>(&mut advents);
@@ -1,5 +1,5 @@
 use crate::utils::{Solve, Label, assert_display};
-use crate::geometry::{Point2D, Direction, Vector, Canvas};
+use crate::geometry::{Point2D, Direction, Vector, Canvas, GridView, Symmetry};
 
 pub(crate) struct Advent {
     label: Label,
@@ -21,25 +21,17 @@ impl Default for Advent {
 }
 
 impl Advent{
-    fn check_match(&self, location: &Vector, word: Option<&Vec<char>>, n_rotations: Option<u8>) -> bool{
-        let (&width, &height) = self.canvas.shape();
+    fn check_match(&self, view: &GridView<char>, location: &Vector, word: Option<&Vec<char>>) -> bool{
         let word = word.unwrap_or(&self.word);
         let n_chars = word.len();
 
-        if location.is_out_of_bounds(n_chars, width, height) {
+        if location.is_out_of_bounds(n_chars, view.width(), view.height()) {
             return false;
         }
 
-        for (e, ch) in word.iter().enumerate() {
-            let mut p = location.get_point(e);
-            if let Some(n) = n_rotations {
-                p = p.rotate90(n, width, height);
-            }
-            if self.canvas.get_element(&p) != Some(ch) {
-                return false;
-            }
-        }
-        true
+        word.iter()
+            .enumerate()
+            .all(|(e, ch)| view.get_element(&location.get_point(e)) == Some(ch))
     }
 }
 
@@ -62,19 +54,16 @@ impl Solve for Advent {
         let first_letter = Some(&self.word[0]);
         let mut count = 0;
         if self.solve_via_rotation{
-            //canvas rotation (slower)
-            let (&w, &h) = self.canvas.shape();
-            assert_eq!(w, h); //via rotation of points of original canvas works only for squares
-            for p in self.canvas.iter() {
-                for n_rotations in 0..4 {
-                    let p_rotated = p.rotate90(n_rotations, w, h);
-                    if first_letter != self.canvas.get_element(&p_rotated) {
+            //search two directions in each rotated view of the canvas (slower)
+            for view in Symmetry::rotations().map(|s| self.canvas.view(s)) {
+                for p in view.points() {
+                    if first_letter != view.get_element(&p) {
                         continue;
                     }
 
-                    for &d in [Direction::Up, Direction::UpRight].iter() {
-                        let shifted_loc = Vector::new(d, **p);
-                        if self.check_match(&shifted_loc, None, Some(n_rotations)) {
+                    for &d in [Direction::Down, Direction::DownRight].iter() {
+                        let shifted_loc = Vector::new(d, p);
+                        if self.check_match(&view, &shifted_loc, None) {
                             count += 1;
                         }
                     }
@@ -82,14 +71,15 @@ impl Solve for Advent {
             }
         }
         else{
-            for p in self.canvas.iter() {
+            let view = self.canvas.view(Symmetry::Identity);
+            for p in self.canvas.points() {
                 if first_letter != self.canvas.get_element(&p) {
                     continue;
                 }
 
                 for d in [&Direction::base()[..], &Direction::diagonal()[..]].concat() {
-                    let shifted_loc = Vector::new(d, **p);
-                    if self.check_match(&shifted_loc, None, None) {
+                    let shifted_loc = Vector::new(d, p);
+                    if self.check_match(&view, &shifted_loc, None) {
                         count += 1;
                     }
                 }
@@ -110,48 +100,46 @@ impl Solve for Advent {
         let mut count = 0;
 
         if self.solve_via_rotation {
-            //canvas rotation (slower)
-            let base_loc = Vector::new(Direction::UpRight, Point2D::new(0, 0));
-            let supp_loc = Vector::new(Direction::DownRight, Point2D::new(0, 2));
-            let (&w, &h) = self.canvas.shape();
-            assert_eq!(w, h); //via rotation of points of original canvas works only for squares
-            for p in self.canvas.iter() {
-                let d = Direction::ToPoint(**p);
-                for n_rotations in 0..4 {
-                    let p_rotated = p.rotate90(n_rotations, w, h);
-                    if first_letter != self.canvas.get_element(&p_rotated) {
+            //a single orientation of the cross in each rotated view of the canvas (slower)
+            let base_loc = Vector::new(Direction::DownRight, Point2D::new(0, 0));
+            let supp_loc = Vector::new(Direction::UpRight, Point2D::new(0, 2));
+            for view in Symmetry::rotations().map(|s| self.canvas.view(s)) {
+                for p in view.points() {
+                    if first_letter != view.get_element(&p) {
                         continue;
                     }
 
-                    if self.check_match(&base_loc.shift(&d), Some(&cut_word), Some(n_rotations)) &&
-                        self.check_match(&supp_loc.shift(&d), Some(&cut_word), Some(n_rotations)) {
+                    let d = Direction::ToPoint(p);
+                    if self.check_match(&view, &base_loc.shift(&d), Some(&cut_word)) &&
+                        self.check_match(&view, &supp_loc.shift(&d), Some(&cut_word)) {
                         count += 1;
                     }
                 }
             }
         }
         else {
+            let view = self.canvas.view(Symmetry::Identity);
             let base_dir = [
-                Direction::DownRight, Direction::UpRight, Direction::UpLeft, Direction::DownLeft
+                Direction::UpRight, Direction::DownRight, Direction::DownLeft, Direction::UpLeft
             ];
 
-            for p in self.canvas.iter() {
+            for p in self.canvas.points() {
                 if first_letter != self.canvas.get_element(&p) {
                     continue;
                 }
 
                 for &d in &base_dir {
-                    let shifted_loc = Vector::new(d, **p);
-                    if self.check_match(&shifted_loc, Some(&cut_word), None) {
+                    let shifted_loc = Vector::new(d, p);
+                    if self.check_match(&view, &shifted_loc, Some(&cut_word)) {
                         let supp_loc = match shifted_loc.direction() {
-                            Direction::DownRight => Vector::new(Direction::DownLeft, Point2D::new(2, 0)),
-                            Direction::UpRight => Vector::new(Direction::DownRight, Point2D::new(0, 2)),
-                            Direction::UpLeft => Vector::new(Direction::UpRight, Point2D::new(-2, 0)),
-                            Direction::DownLeft => Vector::new(Direction::UpLeft, Point2D::new(0, -2)),
+                            Direction::UpRight => Vector::new(Direction::UpLeft, Point2D::new(2, 0)),
+                            Direction::DownRight => Vector::new(Direction::UpRight, Point2D::new(0, 2)),
+                            Direction::DownLeft => Vector::new(Direction::DownRight, Point2D::new(-2, 0)),
+                            Direction::UpLeft => Vector::new(Direction::DownLeft, Point2D::new(0, -2)),
                             _ => Vector::null(),
-                        }.shift(&Direction::ToPoint(**p));
+                        }.shift(&Direction::ToPoint(p));
 
-                        if self.check_match(&supp_loc, Some(&cut_word), None) {
+                        if self.check_match(&view, &supp_loc, Some(&cut_word)) {
                             count += 1;
                         }
                     }
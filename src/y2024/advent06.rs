@@ -1,5 +1,5 @@
 use std::collections::{BTreeSet, HashSet};
-use crate::geometry::{Canvas, Direction, Point2D, Vector};
+use crate::geometry::{Canvas, Direction, Point2D, Recorder, Render, Vector};
 use crate::utils::{Solve, Label, assert_display};
 use rayon::prelude::*;
 
@@ -46,13 +46,7 @@ fn follow_path(
         }
 
         if obstacles.contains(&next_location) {
-            current_direction = match current_direction {
-                Direction::Down => Direction::Right,
-                Direction::Right => Direction::Up,
-                Direction::Up => Direction::Left,
-                Direction::Left => Direction::Down,
-                _ => Direction::None,
-            };
+            current_direction = current_direction.turn_right();
             vector.change_direction(current_direction);
         } else {
             if path_set.contains(&vector) || ordered_path_set.contains(&&vector) {
@@ -89,8 +83,12 @@ impl Solve for Advent {
         let result = self.canvas.try_locate_element(&'^')?.iter().next().map_or(0, |guard_location| {
             let (&w, &h) = self.canvas.shape();
             if let Ok(obstacles) = self.canvas.try_locate_element(&'#') {
-                let obstacles: BTreeSet<Point2D> = obstacles.iter().map(|rc| (**rc).clone()).collect();
-                let (path, is_out_of_bounds) = follow_path(**guard_location, Direction::Down, w, h, &obstacles , None);
+                let (path, is_out_of_bounds) = follow_path(*guard_location, Direction::Up, w, h, obstacles, None);
+                //picture of the route for inspection
+                Recorder::new(self.label.year, "guard").picture("guard_route.txt", |filename| {
+                    let route: Vec<Point2D> = path.iter().map(|v| *v.anchor()).collect();
+                    Render::from_canvas(&self.canvas).path(&route).write_to_file(filename)
+                });
                 let points: HashSet<Point2D> = path
                     .iter()
                     .map(|v| *v.anchor())
@@ -114,8 +112,7 @@ impl Solve for Advent {
         let result = self.canvas.try_locate_element(&'^')?.iter().next().map_or(0, |guard_location| {
             let (&w, &h) = self.canvas.shape();
             if let Ok(obstacles) = self.canvas.try_locate_element(&'#') {
-                let obstacles: BTreeSet<Point2D> = obstacles.iter().map(|rc| (**rc).clone()).collect();
-                let (path, is_out_of_bounds) = follow_path(**guard_location, Direction::Down, w, h, &obstacles, None);
+                let (path, is_out_of_bounds) = follow_path(*guard_location, Direction::Up, w, h, obstacles, None);
                 if !is_out_of_bounds {
                     return 0;
                 }
@@ -1,5 +1,4 @@
 use std::collections::{BTreeSet};
-use std::rc::Rc;
 use crate::geometry::{Canvas, Point2D};
 use crate::utils::{Solve, Label, assert_display};
 
@@ -26,10 +25,10 @@ impl Advent {
              part: u8) -> Result<String, String>{
         self.check_input(Some(part))?;
         let (&w, &h) = self.canvas.shape();
-        let mut antinodes: BTreeSet<Rc<Point2D>> = BTreeSet::new();
+        let mut antinodes: BTreeSet<Point2D> = BTreeSet::new();
 
         for (el, antennas_set) in self.canvas.elements() {
-            if **el == '.' {
+            if *el == '.' {
                 continue;
             }
             let n = antennas_set.len();
@@ -37,22 +36,22 @@ impl Advent {
                 continue;
             }
 
-            let antennas: Vec<Rc<Point2D>> = antennas_set.iter().map(|rc| Rc::clone(rc)).collect();
+            let antennas: Vec<Point2D> = antennas_set.iter().copied().collect();
 
             for i in 0..n - 1 {
                 for j in i + 1..n {
-                    let d = &*antennas[i] - &*antennas[j];
+                    let d = antennas[i] - antennas[j];
 
                     for start in [&antennas[j], &antennas[i]] {
                         let direction = if *start == antennas[j] { 1 } else { -1 };
                         let mut s = 2;
 
                         loop {
-                            let p = &*Rc::clone(start) + &(&d * (s * direction));
+                            let p = *start + d * (s * direction);
                             if p.is_out_of_bounds(w, h) {
                                 break;
                             }
-                            antinodes.insert(Rc::new(p));
+                            antinodes.insert(p);
                             s += 1;
                             if one_step{
                                 break
@@ -80,7 +79,7 @@ impl Solve for Advent {
         self.check_input(None)?;
         println!("Canvas shape: {:?}", self.canvas.shape());
         let mut elements = self.canvas.get_element_set();
-        elements.remove(&Rc::new('.'));
+        elements.remove(&'.');
         println!("Number of antenna types: {}", elements.len() );
         Ok(())
     }
@@ -1,42 +1,15 @@
-use std::collections::{BTreeMap, BTreeSet};
-use crate::utils::{Solve, Label, assert_display};
+use std::collections::BTreeMap;
+use std::ops::Range;
+use crate::utils::{Solve, Label, RangeSet, assert_display};
 
-#[derive(Debug, Clone, Copy)]
-struct Chunk {
-    file_id: Option<usize>,
-    size: usize
-}
-
-impl Chunk {
-    fn new(file_id: Option<usize>, size: usize)->Self{
-        Self {
-            file_id,
-            size
-        }
-    }
-
-    fn checksum_increment(&self, position: usize) -> usize{
-        if let Some(file_id) = self.file_id{
-            file_id*((2 * position + self.size-1)*self.size)/2
-        }else{
-            0
-        }
-    }
-
-    fn reserve_memory(&mut self, other: &Chunk)->Option<Chunk>{
-        let remaining_size = self.size.saturating_sub(other.size);
-        self.size = other.size;
-        if remaining_size > 0 {
-            Some(Chunk::new(self.file_id, remaining_size))
-        } else {
-            None
-        }
-    }
+fn checksum_increment(file_id: usize, blocks: &Range<i64>) -> usize{
+    file_id * ((blocks.start + blocks.end - 1) * (blocks.end - blocks.start)) as usize / 2
 }
 
 pub(crate) struct Advent {
     label: Label,
-    disk_with_chunks: BTreeMap<usize, Chunk>
+    files: Vec<(usize, Range<i64>)>,
+    free_space: RangeSet
 }
 
 
@@ -44,7 +17,8 @@ impl Default for Advent {
     fn default() -> Self {
         Self {
             label: Label::new(9, 2024),
-            disk_with_chunks: BTreeMap::new(), //positions of chunks (free or occupied)
+            files: Vec::new(), //blocks occupied by every file in order of file id
+            free_space: RangeSet::new()
         }
     }
 }
@@ -58,70 +32,41 @@ impl Advent{
              part: u8
     ) -> Result<String, String>{
         self.check_input(Some(part))?;
-        let mut disk = self.disk_with_chunks.clone();
-
-        let files: BTreeMap<usize, usize> = disk
-            .iter()
-            .filter_map(|(&current_position, block)| block.file_id.map(|file_id| (file_id, current_position)))
-            .collect();
-
-        let mut free_chunks: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
-        for (&position, chunk) in disk.iter(){
-            if chunk.file_id.is_none(){
-                free_chunks.entry(chunk.size).or_insert_with(BTreeSet::new).insert(position);
-            }
-        }
+        let mut free_space = self.free_space.clone();
+        //free space only shrinks left of the files still to move, so the leftmost gap
+        //fitting a given size never moves back and the search can resume where it stopped
+        let mut search_from: BTreeMap<i64, i64> = BTreeMap::new();
 
         let mut checksum = 0;
-        for (_, source_position) in files.into_iter().rev(){
-            loop {
-                let source_block = disk.get(&source_position).unwrap();
-
-                if source_block.file_id.is_none(){
-                    break;
-                }
-                let s = if no_split { source_block.size } else { 0 };
-
-                let target_positions: Vec<&usize> = free_chunks
-                    .range(s..)
-                    .filter_map(|(_, v)| v.first())
-                    .filter(|&&p| p < source_position)
-                    .collect();
-
-                if let Some(&&target_position) = target_positions.iter().min() {
-                    if let (Some(mut source_block), Some(mut target_block)) = (
-                        disk.remove(&source_position),
-                        disk.remove(&target_position)
-                    ) {
-                        free_chunks.get_mut(&target_block.size).unwrap().remove(&target_position);
-
-                        if target_block.size >= source_block.size {
-                            // Target has enough space for the source block
-                            if let Some(remainder) = target_block.reserve_memory(&source_block) {
-                                let add_position = target_position + target_block.size;
-                                free_chunks.entry(remainder.size).or_insert_with(BTreeSet::new).insert(add_position);
-                                disk.insert(add_position, remainder);
-                            }
-                            disk.insert(source_position, target_block);
-                        } else {
-                            // Source block needs to be split
-                            if let Some(remainder) = source_block.reserve_memory(&target_block) {
-                                let split_position = source_position + remainder.size;
-                                disk.insert(source_position, remainder);
-                                disk.insert(split_position, target_block);
-                            }else{
-                                disk.insert(source_position, target_block);
-                            }
-                        }
-
-                        checksum += source_block.checksum_increment(target_position);
-                        disk.insert(target_position, source_block);
+        for (file_id, blocks) in self.files.iter().rev(){
+            let mut blocks = blocks.clone();
+            if no_split {
+                let size = blocks.end - blocks.start;
+                let cursor = search_from.entry(size).or_insert(0);
+                let target = free_space.iter_from(*cursor)
+                    .take_while(|gap| gap.start < blocks.start)
+                    .find(|gap| gap.end - gap.start >= size);
+                match target {
+                    Some(gap) => {
+                        *cursor = gap.start;
+                        blocks = gap.start..gap.start + size;
+                        free_space.remove(blocks.clone());
                     }
-                } else {
-                    checksum += source_block.checksum_increment(source_position);
-                    break;
+                    None => *cursor = blocks.start
+                }
+            } else {
+                while !blocks.is_empty() {
+                    let Some(gap) = free_space.iter().next().filter(|gap| gap.start < blocks.start) else {
+                        break;
+                    };
+                    let n = (gap.end - gap.start).min(blocks.end - blocks.start);
+                    let moved = gap.start..gap.start + n;
+                    checksum += checksum_increment(*file_id, &moved);
+                    free_space.remove(moved);
+                    blocks.end -= n;
                 }
             }
+            checksum += checksum_increment(*file_id, &blocks);
         }
         assert_display(checksum, Some(result_test), result_prd, "Checksum", test_mode)
     }
@@ -133,18 +78,17 @@ impl Solve for Advent {
     fn get_label_mut(&mut self) -> &mut Label {&mut self.label}
 
     fn add_record_from_line(&mut self, line: String) -> Result<(), std::num::ParseIntError> {
-        let mut curr_id: usize = 0;
         let mut is_file: bool = true;
-        let mut curr_position: usize = 0;
+        let mut curr_position: i64 = 0;
         for ch in line.chars(){
-            match ch.to_digit(10).map(|d| d as usize){
+            match ch.to_digit(10).map(|d| d as i64){
                 Some(num) =>{
+                    let blocks = curr_position..curr_position + num;
                     if is_file {
-                        self.disk_with_chunks.insert(curr_position, Chunk::new(Some(curr_id), num));
+                        self.files.push((self.files.len(), blocks));
                     }
                     else{
-                        self.disk_with_chunks.insert(curr_position, Chunk::new(None, num));
-                        curr_id+=1;
+                        self.free_space.insert(blocks);
                     }
                     curr_position+=num;
                     is_file = !is_file;
@@ -157,7 +101,7 @@ impl Solve for Advent {
 
     fn info(&self) -> Result<(), String> {
         self.check_input(None)?;
-        println!("Number of chunks {}", self.disk_with_chunks.len());
+        println!("Number of files {}, free blocks {}", self.files.len(), self.free_space.len());
         Ok(())
     }
     fn compute_part1_answer(&self, test_mode: bool) -> Result<String, String>{
@@ -167,4 +111,3 @@ impl Solve for Advent {
         self.solve(true, 2858, 6289564433984, test_mode, 2)
     }
 }
-
@@ -1,5 +1,5 @@
-use std::collections::{HashMap, HashSet};
-use crate::geometry::{Canvas, Direction, Vector};
+use std::collections::HashMap;
+use crate::geometry::{flood_fill, Canvas, Connectivity, Direction, Point2D};
 use crate::utils::{Solve, Label, assert_display};
 
 pub(crate) struct Advent {
@@ -18,71 +18,43 @@ impl Default for Advent {
 }
 
 impl Advent {
-    fn solve(&self,
-             distinct: bool,
-             result_test: usize,
-             result_prd: usize,
-             test_mode: bool,
-             part: u8
-    ) -> Result<String, String>{
-        self.check_input(Some(part))?;
-        let header = if part == 1 {"Sum of scores"} else {"Sum of ratings"};
-
-        let mut score_sum = 0;
-
-        if let Ok(trailheads) = self.canvas.try_locate_element(&'0'){
-            let mut stack: HashSet<Vec<Vector>> = trailheads
-                .iter()
-                .map(|p| vec![Vector::new(Direction::None, **p)])
-                .collect();
-
-            let mut next_dirs: HashMap<Direction, HashSet<Direction>> = Direction::base()
-                .into_iter()
-                .map(|d| {
-                    let mut s: HashSet<Direction> = Direction::base().into_iter().collect();
-                    s.remove(&d.mirror());
-                    (d, s)
-                })
-                .collect();
-            next_dirs.insert(Direction::None, Direction::base().into_iter().collect());
-
-            for i in 1..=9 {
-                let ch_expected = (b'0' + i) as char;
-                let mut next_stack: HashSet<Vec<Vector>> = HashSet::new();
-
-                for ve in stack.iter() {
-                    if let Some (v) = ve.last() {
-                        if let Some(dirs) = next_dirs.get(&v.direction()) {
-                            for &d in dirs.iter() {
-                                let p = v.anchor().get_point(d, 1);
-                                if let Some(&el) = self.canvas.get_element(&p) {
-                                    if el == ch_expected {
-                                        if distinct{
-                                            let mut v_next = ve.clone();
-                                            v_next.push(Vector::new(d, p));
-                                            next_stack.insert(v_next);
-                                        }
-                                        else {
-                                            if let Some(f) = ve.first() {
-                                                if i == 9 {
-                                                    next_stack.insert(vec![f.clone(), Vector::new(Direction::None, p)]);
-                                                } else {
-                                                    next_stack.insert(vec![f.clone(), Vector::new(d, p)]);
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                stack = next_stack;
-            }
-            score_sum = stack.len();
+    //number of summits reachable from every trailhead, climbing one height per step
+    fn scores(&self) -> usize{
+        let Ok(trailheads) = self.canvas.try_locate_element(&'0') else {
+            return 0;
         };
+        trailheads.iter()
+            .filter_map(|p| flood_fill(&self.canvas, p, Connectivity::Four, |&a, &b| b as u32 == a as u32 + 1))
+            .map(|trails| trails.points().iter().filter(|p| self.canvas[*p] == '9').count())
+            .sum()
+    }
 
-        assert_display(score_sum, Some(result_test), result_prd, header, test_mode)
+    //number of distinct hiking trails from every trailhead; the cells of its trails are taken lowest first,
+    //so every trail into a cell has been counted at the neighbour one height below it
+    fn ratings(&self) -> usize{
+        let Ok(trailheads) = self.canvas.try_locate_element(&'0') else {
+            return 0;
+        };
+        let climbs = |a: char, b: char| b as u32 == a as u32 + 1;
+        trailheads.iter()
+            .filter_map(|head| flood_fill(&self.canvas, head, Connectivity::Four, |&a, &b| climbs(a, b)).map(|trails| (head, trails)))
+            .map(|(head, trails)| {
+                let mut cells: Vec<Point2D> = trails.points().iter().copied().collect();
+                cells.sort_by_key(|p| self.canvas[p]);
+                let mut n_trails: HashMap<Point2D, usize> = HashMap::new();
+                for p in cells {
+                    let n = if p == *head { 1 } else {
+                        Direction::base().iter()
+                            .filter_map(|d| self.canvas.step(&p, d))
+                            .filter(|q| climbs(self.canvas[q], self.canvas[&p]))
+                            .filter_map(|q| n_trails.get(&q))
+                            .sum()
+                    };
+                    n_trails.insert(p, n);
+                }
+                n_trails.iter().filter(|(p, _)| self.canvas[*p] == '9').map(|(_, n)| n).sum::<usize>()
+            })
+            .sum()
     }
 }
 
@@ -100,9 +72,11 @@ impl Solve for Advent {
         Ok(())
     }
     fn compute_part1_answer(&self, test_mode: bool) -> Result<String, String>{
-        self.solve(false, 36, 822, test_mode, 1)
+        self.check_input(Some(1))?;
+        assert_display(self.scores(), Some(36), 822, "Sum of scores", test_mode)
     }
     fn compute_part2_answer(&self, test_mode: bool) -> Result<String, String>{
-        self.solve(true, 81, 1801, test_mode, 2)
+        self.check_input(Some(2))?;
+        assert_display(self.ratings(), Some(81), 1801, "Sum of ratings", test_mode)
     }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use crate::geometry::{Direction, Point2D, Vector};
-use crate::utils::{Solve, Label, assert_display, write_vec_to_file};
+use crate::geometry::{Direction, Image, Point2D, Recorder, Rect, Topology, Vector, BLACK};
+use crate::utils::{Solve, Label, assert_display};
 use rayon::prelude::*;
 
 pub(crate) struct Advent {
@@ -60,8 +60,7 @@ impl Solve for Advent {
             let mut sec_elapsed = 0;
             let mut r_curr = r.clone();
             while sec_elapsed < n_seconds {
-                let mut p = r_curr.get_point(1);
-                p.return_into_bounds(width,height);
+                let p = Topology::wrap(&r_curr.get_point(1), width, height).unwrap();
                 r_curr = Vector::new(*r_curr.direction(),p);
                 sec_elapsed +=1;
             }
@@ -69,7 +68,7 @@ impl Solve for Advent {
         }).fold(
             || HashMap::new(), // Initialize empty `HashMap` for each thread
             |mut acc, x| {     // Accumulate counts in each thread's local map
-                *acc.entry(x.anchor().clone()).or_insert(0) += 1;
+                *acc.entry(*x.anchor()).or_insert(0) += 1;
                 acc
             },
         ).reduce(
@@ -112,39 +111,19 @@ impl Solve for Advent {
         let height = DIMS.1;
         let (med_x, med_y) = ((width/2) as isize, (height/2) as isize);
 
-        fn draw(points: &HashSet<&Point2D>,
-                width: usize,
-                height: usize,
-                filename: &str) {
-            let mut rows: Vec<Vec<char>> = Vec::new();
-            for j in 0..height{
-                let mut row: Vec<char> = Vec::new();
-                for i in 0..width{
-                    let p = Point2D::new(i, j);
-                    if points.contains(&p){
-                        row.push('\u{25A0}');
-                    }
-                    else{
-                        row.push('.');
-                    }
-                }
-                rows.push(row);
-            }
-            write_vec_to_file(rows, filename).expect(&format!("Cannot draw {}", filename));
-        }
-
         let mut sec_elapsed: usize = 0;
         let mut robots = self.robots.clone();
         let mut measures: Vec<(usize, f32, usize)> = Vec::new();
+        let mut recorder = Recorder::new(self.label.year, "robots");
         while sec_elapsed < n_seconds {
             robots = robots.iter().map(|r| {
-                let mut p = r.get_point(1);
-                p.return_into_bounds(width, height);
+                let p = Topology::wrap(&r.get_point(1), width, height).unwrap();
                 Vector::new(*r.direction(), p)
             }).collect();
             sec_elapsed += 1;
 
             let points = robots.iter().map(|x| { x.anchor() }).collect::<HashSet<_>>();
+            recorder.record_points(points.iter().copied(), Rect::from_size(width, height), [0, 200, 0], BLACK);
             let mut cnt_points: [usize; DIMS.0] = [0; DIMS.0];
             for p in points.iter() {
                 cnt_points[*p.x() as usize]+=1;
@@ -178,16 +157,19 @@ impl Solve for Advent {
         let _ = fs::create_dir_all(prefix.clone());
         while sec_elapsed < n_seconds {
             robots = robots.iter().map(|r| {
-                let mut p = r.get_point(1);
-                p.return_into_bounds(width, height);
+                let p = Topology::wrap(&r.get_point(1), width, height).unwrap();
                 Vector::new(*r.direction(), p)
             }).collect();
             sec_elapsed += 1;
 
             if suspects.contains(&sec_elapsed){
                 let points = robots.iter().map(|x| { x.anchor() }).collect::<HashSet<_>>();
-                let filename = format!("{}{}.txt", prefix, sec_elapsed);
-                draw(&points, width, height, &filename);
+                let filename = format!("{}{}.png", prefix, sec_elapsed);
+                Image::blank(Rect::from_size(width, height), BLACK)
+                    .plot(points, [0, 200, 0])
+                    .scaled(4)
+                    .write_to_file(&filename)
+                    .unwrap_or_else(|_| panic!("Cannot draw {}", filename));
             }
         }
         
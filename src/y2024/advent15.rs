@@ -1,13 +1,12 @@
-use std::collections::{BTreeSet, HashSet};
-use std::rc::Rc;
-use crate::geometry::{Canvas, Direction, Point2D};
+use std::collections::HashSet;
+use crate::geometry::{Canvas, Direction, Palette, Point2D, Recorder, BLACK};
 use crate::utils::{Solve, Label, assert_display};
 
 pub(crate) struct Advent {
     label: Label,
     canvas: Canvas,
     read_canvas: bool,
-    commands: String
+    commands: Vec<Direction>
 }
 
 
@@ -17,11 +16,71 @@ impl Default for Advent {
             label: Label::new(15, 2024),
             canvas: Canvas::default(),
             read_canvas: true,
-            commands: String::from("")
+            commands: Vec::new()
         }
     }
 }
 
+impl Advent {
+    //cells pushed by the robot standing at start, the robot first and the farthest ones last;
+    //None when something hits a wall
+    fn pushed_cells(warehouse: &Canvas, start: &Point2D, d: &Direction) -> Option<Vec<Point2D>>{
+        let mut pushed = vec![*start];
+        let mut seen = HashSet::from([*start]);
+        let mut i = 0;
+        while i < pushed.len() {
+            let next = &pushed[i] + d;
+            let blocks = match warehouse[&next] {
+                '#' => return None,
+                'O' => vec![next],
+                '[' => vec![next, &next + &Direction::Right],
+                ']' => vec![next, &next + &Direction::Left],
+                _ => vec![]
+            };
+            for p in blocks {
+                if seen.insert(p) {
+                    pushed.push(p);
+                }
+            }
+            i += 1;
+        }
+        Some(pushed)
+    }
+
+    //moves the robot and the boxes inside the warehouse and sums the GPS coordinates of the boxes
+    fn simulate(&self, mut warehouse: Canvas, name: &str) -> Result<usize, String>{
+        let points = warehouse.try_locate_element(&'@')?;
+        if points.len() != 1 {
+            return Err(String::from("Multiple robot locations"));
+        }
+        let mut robot_position = *points.first().unwrap();
+        let palette = Palette::new(BLACK)
+            .with('#', [128, 128, 128])
+            .with('@', [255, 0, 0])
+            .with('O', [200, 150, 0])
+            .with('[', [200, 150, 0])
+            .with(']', [200, 150, 0]);
+        let mut recorder = Recorder::new(self.label.year, name);
+        recorder.record_grid(&warehouse, |el| palette.color(el));
+        for d in self.commands.iter(){
+            if let Some(pushed) = Self::pushed_cells(&warehouse, &robot_position, d) {
+                for p in pushed.iter().rev() {
+                    warehouse.move_element(p, &(p + d), '.');
+                }
+                robot_position = &robot_position + d;
+            }
+            recorder.record_grid(&warehouse, |el| palette.color(el));
+        }
+        let gps_sum: isize = ['O', '[']
+            .iter()
+            .filter_map(|el| warehouse.elements().get(el))
+            .flatten()
+            .map(|p| p.x() + p.y() * 100)
+            .sum();
+        Ok(gps_sum as usize)
+    }
+}
+
 impl Solve for Advent {
     fn get_label(&self) -> &Label{ &self.label }
     fn get_label_mut(&mut self) -> &mut Label {&mut self.label}
@@ -34,7 +93,11 @@ impl Solve for Advent {
             if self.read_canvas {
                 self.canvas.add_row(line.chars().collect());
             } else {
-                self.commands.push_str(&line);
+                let Some(commands) = line.chars().map(|ch| Direction::try_from_char(&ch)).collect::<Option<Vec<_>>>() else {
+                    "invalid".parse::<i32>()?;
+                    return Ok(());
+                };
+                self.commands.extend(commands);
             }
         }
         Ok(())
@@ -48,206 +111,25 @@ impl Solve for Advent {
     }
     fn compute_part1_answer(&self, test_mode: bool) -> Result<String, String>{
         self.check_input(Some(1))?;
-        let points = self.canvas.try_locate_element(&'@')?;
-        let obstacles = self.canvas.try_locate_element(&'#')?;
-        let boxes = self.canvas.try_locate_element(&'O')?;
-        if points.len()==1{
-            let mut robot_position = points.first().unwrap().clone();
-            let mut movable_boxes = boxes.clone();
-            for c in self.commands.chars(){
-                let d = match c{
-                            '>' => Direction::Right,
-                            'v' => Direction::Up,
-                            '<' => Direction::Left,
-                            '^' => Direction::Down,
-                            _ => unreachable!()
-                };
-                let test_position = &robot_position+&d;
-                if obstacles.contains(&test_position){
-                    continue;
-                }else{
-                    if movable_boxes.contains(&test_position){
-                        let mut moved_boxes_before: HashSet<Rc<Point2D>> = HashSet::new();
-                        let mut moved_boxes_after: HashSet<Rc<Point2D>> = HashSet::new();
-                        let mut p = test_position.clone();
-                        let mut could_move: bool = true;
-                        loop{
-                            moved_boxes_before.insert(p.clone());
-                            let p_moved = &p + &d;
-                            moved_boxes_after.insert(p_moved.clone());
-                            if obstacles.contains(&p_moved) {
-                                could_move = false;
-                                break;
-                            }
-                            if movable_boxes.contains(&p_moved){
-                                p = p_moved;
-                            }
-                            else{
-                                break;
-                            }
-                        }
-                        if could_move{
-                            movable_boxes.retain(|x| !moved_boxes_before.contains(x));
-                            movable_boxes.extend(moved_boxes_after);
-                            robot_position = test_position;
-                        }
-                    }else{
-                        robot_position = test_position;
-                    }
-                }
-            }
-            let gps_sum: isize = movable_boxes
-                .iter()
-                .map(|p| p.x() + p.y() * 100)
-                .sum();
-            assert_display(gps_sum as usize, Some(10092), 1552879, "Sum of boxes GPS coordinates", test_mode)
-        }else{
-            Err(String::from("Multiple robot locations"))
-        }
+        let gps_sum = self.simulate(self.canvas.clone(), "warehouse_part1")?;
+        assert_display(gps_sum, Some(10092), 1552879, "Sum of boxes GPS coordinates", test_mode)
     }
     fn compute_part2_answer(&self, test_mode: bool) -> Result<String, String>{
         self.check_input(Some(2))?;
-        let mut canvas_streched = Canvas::default();
-        let (width, height) = self.canvas.shape();
-        for j in 0..*height{
-            let mut row: Vec<char> = Vec::new();
-            for i in 0..*width{
-                match *self.canvas.get_element(&Point2D::new(i,j)).unwrap(){
-                    '#' => {row.push('#'); row.push('#');},
-                    '.' => {row.push('.'); row.push('.');},
-                    'O' => {row.push('['); row.push(']');},
-                    '@' => {row.push('@'); row.push('.');},
-                    _ => unreachable!()
-                }
-            }
-            canvas_streched.add_row(row);
-        }
-        let points = canvas_streched.try_locate_element(&'@')?;
-        let obstacles = canvas_streched.try_locate_element(&'#')?;
-        let boxes_left = canvas_streched.try_locate_element(&'[')?;
-        let boxes_right = canvas_streched.try_locate_element(&']')?;
-        if points.len()==1{
-            let mut robot_position = points.first().unwrap().clone();
-            type LabeledPoint = (Rc<Point2D>, char);
-            fn reverse_label(label: &char)->char{
-                if *label == ']'{
-                    '['
-                }
-                else{
-                    ']'
-                }
-            }
-            let mut movable_boxes = boxes_left
+        let canvas_streched = Canvas::from_rows(self.canvas
+            .rows()
+            .map(|row| row
                 .iter()
-                .map(|e| (e.clone(), '['))
-                .chain(boxes_right.iter().map(|e| (e.clone(), ']')))
-                .collect::<BTreeSet<_>>();
-            
-            for c in self.commands.chars(){
-                let d = match c{
-                    '>' => Direction::Right,
-                    'v' => Direction::Up,
-                    '<' => Direction::Left,
-                    '^' => Direction::Down,
+                .flat_map(|el| match el {
+                    '#' => ['#', '#'],
+                    '.' => ['.', '.'],
+                    'O' => ['[', ']'],
+                    '@' => ['@', '.'],
                     _ => unreachable!()
-                };
-                let test_position = &robot_position+&d;
-                if obstacles.contains(&test_position){
-                    continue;
-                }else{
-                    if movable_boxes.contains( &(test_position.clone(),']')) || movable_boxes.contains( &(test_position.clone(),'[')){
-                        let mut moved_boxes_before: HashSet<LabeledPoint> = HashSet::new();
-                        let mut moved_boxes_after: HashSet<LabeledPoint> = HashSet::new();
-                        let mut could_move: bool = true;
-                        match d {
-                            Direction::Right | Direction::Left => {
-                                let label= if movable_boxes.contains( &(test_position.clone(),']')){
-                                    ']'
-                                }
-                                else{
-                                    '['
-                                };
-                                let mut p= (test_position.clone(), label);
-                                loop{
-                                    let start_moved = &p.0 + &d;
-                                    let end_moved = &p.0 + &(&d*2);
-                                    moved_boxes_before.extend([p, (start_moved.clone(), reverse_label(&label))]);
-                                    moved_boxes_after.extend([(start_moved.clone(), label), (end_moved.clone(), reverse_label(&label))]);
-                                    if obstacles.contains(&end_moved){
-                                        could_move = false;
-                                        break;
-                                    }
-                                    if movable_boxes.contains( &(end_moved.clone(), label)){
-                                        p = (end_moved, label);
-                                    }
-                                    else{
-                                        break;
-                                    }
-                                }
-                            },
-                            Direction::Up | Direction::Down =>{
-                                let mut stack: Vec<LabeledPoint> = Vec::new();
-
-                                if movable_boxes.contains( &(test_position.clone(),']')){
-                                    stack.push((test_position.clone(),']'));
-                                    stack.push((&test_position+&Direction::Left,'['));
-                                }
-                                else{
-                                    stack.push((test_position.clone(),'['));
-                                    stack.push((&test_position+&Direction::Right,']'));
-                                }
-                                loop{
-                                    let mut stack_next: Vec<LabeledPoint> = Vec::new();
-                                    for (p, label) in stack.iter(){
-                                        let p_moved = p + &d;
-                                        moved_boxes_after.insert((p_moved.clone(), *label));
-                                        if obstacles.contains(&p_moved){
-                                            could_move = false;
-                                            break;
-                                        }
-                                        if movable_boxes.contains( &(p_moved.clone(), *label)){
-                                            stack_next.push((p_moved.clone(), *label));
-                                        }
-                                        let rev_label = reverse_label(label);
-                                        if movable_boxes.contains( &(p_moved.clone(), rev_label)){
-                                            stack_next.push((p_moved.clone(), rev_label));
-                                            if rev_label == '[' {
-                                                stack_next.push((&p_moved + &Direction::Right, ']'));
-                                            }
-                                            else{
-                                                stack_next.push((&p_moved + &Direction::Left, '['));
-                                            }
-                                        }
-                                    }
-                                    moved_boxes_before.extend(stack);
-                                    stack=stack_next;
-                                    if stack.is_empty(){
-                                        break;
-                                    }
-                                }
-
-                            },
-                            _ => unreachable!()
-                        };
-                        if could_move{
-                            movable_boxes.retain(|x| !moved_boxes_before.contains(x));
-                            movable_boxes.extend(moved_boxes_after);
-                            robot_position = test_position;
-                        }
-                    }else{
-                        robot_position = test_position;
-                    }
-                }
-            }
-
-            let gps_sum: isize = movable_boxes
-                .iter()
-                .filter(|(_, label)| *label == '[')
-                .map(|(p, _)| p.x() + p.y() * 100)
-                .sum();
-            assert_display(gps_sum as usize, Some(9021), 1561175, "Sum of boxes GPS coordinates", test_mode)
-        }else{
-            Err(String::from("Multiple robot locations"))
-        }
+                })
+                .collect())
+            .collect());
+        let gps_sum = self.simulate(canvas_streched, "warehouse_part2")?;
+        assert_display(gps_sum, Some(9021), 1561175, "Sum of boxes GPS coordinates", test_mode)
     }
-}
\ No newline at end of file
+}
@@ -1,7 +1,7 @@
 use std::cmp::{Reverse};
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::rc::Rc;
-use crate::geometry::{Canvas, Direction, Point2D, ScoredPosition};
+use crate::geometry::{Canvas, Direction, Point2D, Recorder, Render, ScoredPosition};
 use crate::utils::{Solve, Label, assert_display};
 
 
@@ -36,7 +36,7 @@ impl Advent {
         if start.len()==1 && finish.len()==1 {
             let finish_pos = finish.first().unwrap();
             let start_dir = Direction::Right;
-            let start_pos = start.first().unwrap().clone();
+            let start_pos = Rc::new(*start.first().unwrap());
             let mut visited: HashMap<(Direction, Rc<Point2D>), usize> = HashMap::new();
 
             let mut queue: BinaryHeap<Reverse<ScoredPosition>> = BinaryHeap::new();
@@ -61,14 +61,14 @@ impl Advent {
                     if p.score > s {
                         break;
                     }
-                    if p.score == s && p.location == *finish_pos {
+                    if p.score == s && *p.location == *finish_pos {
                         points.extend(p.path.iter().cloned());
                     }
                 }
 
                 visited.insert((p.direction, p.location.clone()), p.score);
 
-                if p.location == *finish_pos {
+                if *p.location == *finish_pos {
                     min_score = Some(p.score);
                     if collect_paths {
                         points.extend(p.path.iter().cloned());
@@ -77,16 +77,12 @@ impl Advent {
                     }
                 }
 
-                let next_dirs = match p.direction {
-                    Direction::Up | Direction::Down => [p.direction, Direction::Left, Direction::Right],
-                    Direction::Right | Direction::Left => [p.direction, Direction::Up, Direction::Down],
-                    _ => unreachable!(),
-                };
+                let next_dirs = [p.direction, p.direction.turn_left(), p.direction.turn_right()];
 
                 next_dirs.iter().for_each(|&d| {
                     let next_p = &p.location + &d;
 
-                    if !obstacles.contains(&next_p) {
+                    if !obstacles.contains(&*next_p) {
                         let next_score = if d == p.direction { 1 } else { 1001 } + p.score;
                         let mut continue_path = true;
 
@@ -112,6 +108,11 @@ impl Advent {
             }
             if let Some(min_score) = min_score {
                 let result = if collect_paths {
+                    //picture of the tiles on the best paths for inspection
+                    Recorder::new(self.label.year, "maze").picture("best_tiles.txt", |filename| {
+                        let tiles: Vec<Point2D> = points.iter().map(|p| **p).collect();
+                        Render::from_canvas(&self.canvas).overlay(&tiles, 'O').write_to_file(filename)
+                    });
                     points.len()
                 }else{
                     min_score
@@ -1,7 +1,4 @@
-use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap, HashSet};
-use std::rc::Rc;
-use crate::geometry::{Direction, Point2D, ScoredPosition};
+use crate::geometry::{Grid, GridLike, Point2D, Recorder, Rect, Render};
 use crate::utils::{Solve, Label, assert_display};
 
 
@@ -22,49 +19,18 @@ impl Default for Advent {
 }
 
 impl Advent {
-    fn shortest_path(&self,
-                     borders: &HashSet<Rc<Point2D>>,
-                     n_bytes: usize,
-                     start_pos: &Rc<Point2D>,
-                     finish_pos: &Rc<Point2D>) -> Option<usize>{
-        let mut obstacles: HashSet<Rc<Point2D>> = borders.iter().cloned().collect();
-        for i in 0..n_bytes {
-            obstacles.insert(Rc::new(self.bytes[i]));
-        }
-
-        let mut visited: HashMap<Rc<Point2D>, usize> = HashMap::new();
-        let mut queue: BinaryHeap<Reverse<ScoredPosition>> = BinaryHeap::new();
-        queue.push(Reverse(ScoredPosition::simple(0, start_pos.clone())));
-        let mut min_score: Option<usize> = None;
-
-        while let Some(Reverse(p)) = queue.pop() {
-
-            if p.location == *finish_pos {
-                min_score = Some(p.score);
-                break;
-            }
-
-            Direction::base().iter().for_each(|&d| {
-                let next_p = &p.location + &d;
-
-                if !obstacles.contains(&next_p) {
-                    let next_score = 1 + p.score;
-                    let mut continue_path = true;
-
-                    if let Some(s) = visited.get(&next_p.clone()) {
-                        if next_score >= *s {
-                            continue_path = false;
-                        }
-                    }
+    //memory space after the first n_bytes have fallen, true where a byte is
+    fn memory(&self, space: &Rect, n_bytes: usize) -> Grid<bool>{
+        let mut memory = Grid::new(space.width(), space.height(), false);
+        memory.update(self.bytes[..n_bytes].iter().map(|&p| (p, true)));
+        memory
+    }
 
-                    if continue_path {
-                        visited.insert(next_p.clone(), next_score);
-                        queue.push(Reverse(ScoredPosition::simple(next_score, next_p)));
-                    }
-                }
-            });
-        }
-        min_score
+    fn shortest_path(&self, space: &Rect, n_bytes: usize) -> Option<usize>{
+        self.memory(space, n_bytes)
+            .distances(space.min_corner(), |&corrupted| !corrupted)
+            .get(space.max_corner())
+            .copied()
     }
 }
 
@@ -86,11 +52,8 @@ impl Solve for Advent {
     }
     fn compute_part1_answer(&self, test_mode: bool) -> Result<String, String>{
         self.check_input(Some(1))?;
-        let (n_bytes,
-            obstacles,
-            start_pos,
-            finish_pos) = initialize(test_mode);
-        let min_score = self.shortest_path(&obstacles, n_bytes, &start_pos, &finish_pos);
+        let (n_bytes, space) = initialize(test_mode);
+        let min_score = self.shortest_path(&space, n_bytes);
         if let Some(min_score) = min_score {
             assert_display(min_score, Some(22), 348, "Shortest path", test_mode)
         }
@@ -101,20 +64,17 @@ impl Solve for Advent {
 
     fn compute_part2_answer(&self, test_mode: bool) -> Result<String, String>{
         self.check_input(Some(2))?;
-        let (n_bytes,
-            obstacles,
-            start_pos,
-            finish_pos) = initialize(test_mode);
+        let (n_bytes, space) = initialize(test_mode);
 
         let mut n_bytes_low = n_bytes;
         let mut n_bytes_high = self.bytes.len();
 
-        let mut r_low = self.shortest_path(&obstacles, n_bytes_low, &start_pos, &finish_pos);
-        let mut r_high = self.shortest_path(&obstacles, n_bytes_high, &start_pos, &finish_pos);
+        let mut r_low = self.shortest_path(&space, n_bytes_low);
+        let mut r_high = self.shortest_path(&space, n_bytes_high);
 
         while n_bytes_high - n_bytes_low > 1 {
             let n_bytes_mid = (n_bytes_low + n_bytes_high) / 2;
-            let r_mid = self.shortest_path(&obstacles, n_bytes_mid, &start_pos, &finish_pos);
+            let r_mid = self.shortest_path(&space, n_bytes_mid);
 
             match (r_low.is_some(), r_mid.is_some(), r_high.is_some()) {
                 (true, true, false) => {
@@ -130,27 +90,23 @@ impl Solve for Advent {
         }
 
         let result = self.bytes[n_bytes_low];
+        //picture of the memory space at the moment the exit gets cut off
+        Recorder::new(self.label.year, "memory").picture("blocking_byte.txt", |filename| {
+            Render::blank(space)
+                .overlay(&self.bytes[..n_bytes_low], '#')
+                .overlay([&result], 'X')
+                .write_to_file(filename)
+        });
         assert_display(result, Some(Point2D::new(6,1)), Point2D::new(54,44), "Blocking byte", test_mode)
     }
 }
 
-fn initialize(test_mode: bool) -> (usize, HashSet<Rc<Point2D>>, Rc<Point2D>, Rc<Point2D>) {
+//number of bytes fallen for part 1 and the memory space, exit at its far corner
+fn initialize(test_mode: bool) -> (usize, Rect) {
     let (width, height, n_bytes): (usize, usize, usize) = if test_mode{
         (7, 7, 12)
     }else{
         (71, 71, 1024)
     };
-    let mut obstacles: HashSet<Rc<Point2D>> = HashSet::new();
-    //insert boarders
-    for i in 0..width{
-        obstacles.insert(Rc::new(Point2D::new(i as isize,-1)));
-        obstacles.insert(Rc::new(Point2D::new(i as isize, height as isize)));
-    }
-    for i in 0..height{
-        obstacles.insert(Rc::new(Point2D::new(-1, i as isize)));
-        obstacles.insert(Rc::new(Point2D::new(width as isize, i as isize)));
-    }
-    let finish_pos = Rc::new(Point2D::new((width - 1) as isize, (height - 1) as isize));
-    let start_pos = Rc::new(Point2D::new(0, 0));
-    (n_bytes, obstacles, start_pos, finish_pos)
-}
\ No newline at end of file
+    (n_bytes, Rect::from_size(width, height))
+}
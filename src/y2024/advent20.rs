@@ -1,12 +1,11 @@
 use std::collections::{BTreeSet, HashMap, HashSet};
-use std::sync::Arc;
 use rayon::prelude::*;
-use crate::geometry::{CanvasAsync, Direction, Point2D};
+use crate::geometry::{Canvas, Direction, Point2D};
 use crate::utils::{Solve, Label, assert_display};
 
 pub(crate) struct Advent {
     label: Label,
-    canvas: CanvasAsync,
+    canvas: Canvas,
 }
 
 
@@ -14,23 +13,23 @@ impl Default for Advent {
     fn default() -> Self {
         Self {
             label: Label::new(20, 2024),
-            canvas: CanvasAsync::default(),
+            canvas: Canvas::default(),
         }
     }
 }
 
 impl Advent {
-    fn shortest_path(&self, obstacles: &BTreeSet<Arc<Point2D>>, start_pos: &Arc<Point2D>, n_steps: Option<usize>) -> HashMap<Arc<Point2D>, usize>
+    fn shortest_path(&self, obstacles: &BTreeSet<Point2D>, start_pos: &Point2D, n_steps: Option<usize>) -> HashMap<Point2D, usize>
     {
-        let mut visited: HashMap<Arc<Point2D>, usize> = HashMap::new();
+        let mut visited: HashMap<Point2D, usize> = HashMap::new();
         let mut step: usize = 0;
-        visited.insert(start_pos.clone(), step);
-        let mut stack: HashSet<Arc<Point2D>> = HashSet::new();
-        stack.insert(start_pos.clone());
+        visited.insert(*start_pos, step);
+        let mut stack: HashSet<Point2D> = HashSet::new();
+        stack.insert(*start_pos);
         loop{
             step+=1;
 
-            let mut next_stack: HashSet<Arc<Point2D>> = HashSet::new();
+            let mut next_stack: HashSet<Point2D> = HashSet::new();
             for p in stack.iter(){
                 Direction::base().iter().for_each(|&d| {
                     let next_p = p + &d;
@@ -40,8 +39,8 @@ impl Advent {
                         } else {
                             true
                         };
-                        if visited.get(&next_p.clone()).is_none() && continue_search {
-                            visited.insert(next_p.clone(), step);
+                        if !visited.contains_key(&next_p) && continue_search {
+                            visited.insert(next_p, step);
                             next_stack.insert(next_p);
                         }
                     }
@@ -80,14 +79,14 @@ impl Advent {
 
             if let Some(&benchmark) = visited_start.get(finish_pos) {
                 let result: usize = visited_start.iter().collect::<Vec<_>>().par_iter().map(|(cheat_entry, &s_dist)|{
-                    let mut cheats: HashMap<(Arc<Point2D>, Arc<Point2D>), usize> = HashMap::new();
-                    let reachable = self.shortest_path(&BTreeSet::new(), &cheat_entry, Some(max_cheats));
+                    let mut cheats: HashMap<(Point2D, Point2D), usize> = HashMap::new();
+                    let reachable = self.shortest_path(&BTreeSet::new(), cheat_entry, Some(max_cheats));
                     for (p, &length) in reachable.iter() {
                         for fd in Direction::base() {
                             let cheat_exit = p + &fd;
                             if let Some(&f_dist) = visited_finish.get(&cheat_exit) {
                                 let gain = benchmark.saturating_sub(f_dist + s_dist + length + 1);
-                                let cheat_key = ((*cheat_entry).clone(), cheat_exit.clone());
+                                let cheat_key = (**cheat_entry, cheat_exit);
                                 if gain>threshold {
                                     let insert_cheat = if let Some(max_gain) = cheats.get(&cheat_key){
                                         gain>*max_gain
@@ -119,7 +118,7 @@ impl Solve for Advent {
     fn get_label(&self) -> &Label { &self.label }
     fn get_label_mut(&mut self) -> &mut Label { &mut self.label }
 
-    fn get_canvas_async_mut(&mut self) -> Option<&mut CanvasAsync> {
+    fn get_canvas_mut(&mut self) -> Option<&mut Canvas> {
         Some(&mut self.canvas)
     }
 
@@ -158,7 +158,7 @@ fn process_pair(from: &char, to: &char, keypad: &Keypad) -> Option<Vec<Vec<char>
                             .or_insert_with(Vec::new)
                             .extend(next_seqs.iter_mut().map(|seq| {
                                 let mut seq = seq.clone();
-                                seq.push(d.to_char());
+                                seq.push(d.to_char().unwrap());
                                 seq
                             }));
                     }
@@ -1,5 +1,5 @@
 use std::collections::HashSet;
-use crate::utils::{Solve, Label, read_lines, assert_display};
+use crate::utils::{Solve, Label, assert_display};
 
 pub(crate) struct Advent {
     label: Label,
@@ -22,56 +22,52 @@ impl Solve for Advent {
     fn get_label(&self) -> &Label{ &self.label }
     fn get_label_mut(&mut self) -> &mut Label {&mut self.label}
 
-    fn read_input(&mut self, test_mode: bool) -> Result<(), std::num::ParseIntError>{
-        let filename = self.get_label().get_puzzle_input_path(test_mode);
-
-        if let Ok(lines) = read_lines(filename) {
-            let mut is_lock: Option<bool> = None;
-            let mut entry: [u8;5] = [0; 5];
-            for line in lines.flatten() {
-                if line.is_empty() {
-                    if let Some(is_lock) = is_lock {
-                        if is_lock {
-                            self.locks.insert(entry.clone());
+    fn read_input_from_str(&mut self, content: &str) -> Result<(), std::num::ParseIntError>{
+        let mut is_lock: Option<bool> = None;
+        let mut entry: [u8;5] = [0; 5];
+        for line in content.lines() {
+            if line.is_empty() {
+                if let Some(is_lock) = is_lock {
+                    if is_lock {
+                        self.locks.insert(entry);
+                    } else {
+                        self.keys.insert(entry);
+                    }
+                }
+                is_lock = None;
+            } else {
+                match is_lock {
+                    None => {
+                        if line.starts_with('#') {
+                            entry = [0; 5];
+                            is_lock = Some(true);
                         } else {
-                            self.keys.insert(entry.clone());
+                            entry = [5; 5];
+                            is_lock = Some(false);
                         }
                     }
-                    is_lock = None;
-                } else {
-                    match is_lock {
-                        None => {
-                            if line.starts_with('#') {
-                                entry = [0; 5];
-                                is_lock = Some(true);
-                            } else {
-                                entry = [5; 5];
-                                is_lock = Some(false);
-                            }
-                        }
-                        Some(is_lock) => {
-                            for (v, ch) in entry.iter_mut().zip(line.chars()) {
-                                if is_lock {
-                                    if ch == '#' {
-                                        *v += 1;
-                                    }
-                                } else if ch == '.' {
-                                    *v -= 1;
+                    Some(is_lock) => {
+                        for (v, ch) in entry.iter_mut().zip(line.chars()) {
+                            if is_lock {
+                                if ch == '#' {
+                                    *v += 1;
                                 }
+                            } else if ch == '.' {
+                                *v -= 1;
                             }
                         }
                     }
                 }
             }
-            if let Some(is_lock) = is_lock {
-                if is_lock {
-                    self.locks.insert(entry.clone());
-                } else {
-                    self.keys.insert(entry.clone());
-                }
+        }
+        if let Some(is_lock) = is_lock {
+            if is_lock {
+                self.locks.insert(entry);
+            } else {
+                self.keys.insert(entry);
             }
-            self.get_label_mut().has_input = true;
         }
+        self.get_label_mut().has_input = true;
         Ok(())
     }
     fn info(&self) -> Result<(), String> {